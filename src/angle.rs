@@ -0,0 +1,178 @@
+use std::f64::consts::TAU;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Neg};
+use crate::wrap_float::WrapFloat;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Angle(WrapFloat<f64>);
+
+// Angle wrapping into [0, 360) degrees / [0, 2π) radians.
+// Degrees are the only stored representation; radians are computed on the
+// way in and out, so converting back and forth doesn't accumulate error.
+impl Angle {
+    pub fn from_degrees(degrees: f64) -> Angle {
+        Angle(WrapFloat::new(degrees, 360.0))
+    }
+
+    pub fn from_radians(radians: f64) -> Angle {
+        Angle::from_degrees(radians.to_degrees())
+    }
+
+    pub fn to_degrees(self) -> f64 {
+        self.0.get_value()
+    }
+
+    pub fn to_radians(self) -> f64 {
+        let radians = self.0.get_value().to_radians();
+
+        if radians >= TAU { 0.0 } else { radians }
+    }
+
+    pub fn sin(self) -> f64 {
+        self.to_radians().sin()
+    }
+
+    pub fn cos(self) -> f64 {
+        self.to_radians().cos()
+    }
+
+    pub fn tan(self) -> f64 {
+        self.to_radians().tan()
+    }
+
+    // Shortest signed difference from `self` to `other` in degrees, in [-180, 180).
+    pub fn difference(self, other: Angle) -> f64 {
+        self.0.signed_distance(other.0)
+    }
+}
+
+impl From<Angle> for WrapFloat<f64> {
+    fn from(angle: Angle) -> Self {
+        angle.0
+    }
+}
+
+impl Add for Angle {
+    type Output = Self;
+
+    fn add(self, rhs: Angle) -> Self::Output {
+        Angle(self.0 + rhs.to_degrees())
+    }
+}
+
+impl AddAssign for Angle {
+    fn add_assign(&mut self, rhs: Angle) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Angle {
+    type Output = Self;
+
+    fn sub(self, rhs: Angle) -> Self::Output {
+        Angle(self.0 - rhs.to_degrees())
+    }
+}
+
+impl SubAssign for Angle {
+    fn sub_assign(&mut self, rhs: Angle) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Angle {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Angle::from_degrees(-self.to_degrees())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, FRAC_PI_2};
+
+    #[test]
+    fn negative_degrees_eq() {
+        assert_eq!(Angle::from_degrees(-90.0), Angle::from_degrees(270.0));
+    }
+
+    #[test]
+    fn from_radians_normalizes() {
+        let angle = Angle::from_radians(-FRAC_PI_2);
+
+        assert!((angle.to_degrees() - 270.0).abs() < 1e-12);
+        assert!((angle.to_radians() - 3.0 * FRAC_PI_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn add_wraps() {
+        let angle = Angle::from_degrees(350.0) + Angle::from_degrees(20.0);
+
+        assert_eq!(angle.to_degrees(), 10.0);
+    }
+
+    #[test]
+    fn sub_wraps() {
+        let mut angle = Angle::from_degrees(10.0);
+
+        angle -= Angle::from_degrees(20.0);
+
+        assert_eq!(angle.to_degrees(), 350.0);
+    }
+
+    #[test]
+    fn neg() {
+        assert_eq!(-Angle::from_degrees(90.0), Angle::from_degrees(270.0));
+        assert_eq!(-Angle::from_degrees(0.0), Angle::from_degrees(0.0));
+    }
+
+    #[test]
+    fn difference_across_seam() {
+        let angle1 = Angle::from_degrees(350.0);
+        let angle2 = Angle::from_degrees(10.0);
+
+        assert_eq!(angle1.difference(angle2), 20.0);
+        assert_eq!(angle2.difference(angle1), -20.0);
+    }
+
+    #[test]
+    fn trig() {
+        let angle = Angle::from_degrees(90.0);
+
+        assert!((angle.sin() - 1.0).abs() < 1e-12);
+        assert!(angle.cos().abs() < 1e-12);
+        assert!((Angle::from_degrees(45.0).tan() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn degree_radian_round_trip() {
+        for i in 0..3600 {
+            let degrees = i as f64 * 0.1;
+
+            let radians = Angle::from_degrees(degrees).to_radians();
+            let back = Angle::from_radians(radians).to_degrees();
+
+            assert!((back - degrees).abs() < 1e-12, "{} became {}", degrees, back);
+        }
+    }
+
+    #[test]
+    fn radian_degree_round_trip() {
+        for i in 0..1000 {
+            let radians = i as f64 * 2.0 * PI / 1000.0;
+
+            let back = Angle::from_radians(radians).to_radians();
+
+            assert!((back - radians).abs() < 1e-12, "{} became {}", radians, back);
+        }
+    }
+
+    #[test]
+    fn into_wrap_float() {
+        let num: WrapFloat<f64> = Angle::from_degrees(-90.0).into();
+
+        assert_eq!(num.get_value(), 270.0);
+        assert_eq!(num.get_wrap(), 360.0);
+    }
+}
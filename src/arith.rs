@@ -0,0 +1,86 @@
+// Overflow-free modular helpers on u128, shared by the wrapping types.
+// All operands except the multiplier in `mul_mod` must already be below `m`.
+
+pub(crate) fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if b >= m - a {
+        b - (m - a)
+    } else {
+        a + b
+    }
+}
+
+pub(crate) fn sub_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+pub(crate) fn mul_mod(a: u128, mut b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    let mut a = a % m;
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+
+    result
+}
+
+// Euclidean remainder of a signed value, i.e. always in [0, m).
+pub(crate) fn rem_euclid_i128(x: i128, m: u128) -> u128 {
+    if x >= 0 {
+        x as u128 % m
+    } else {
+        let d = x.unsigned_abs() % m;
+        if d == 0 { 0 } else { m - d }
+    }
+}
+
+// High 128 bits of the full 256-bit product `a * b`.
+pub(crate) fn mul_hi(a: u128, b: u128) -> u128 {
+    let (a_hi, a_lo) = (a >> 64, a as u64 as u128);
+    let (b_hi, b_lo) = (b >> 64, b as u64 as u128);
+
+    let lo = a_lo * b_lo;
+    let mid1 = a_hi * b_lo;
+    let mid2 = a_lo * b_hi;
+    let carry = ((lo >> 64) + (mid1 as u64 as u128) + (mid2 as u64 as u128)) >> 64;
+
+    a_hi * b_hi + (mid1 >> 64) + (mid2 >> 64) + carry
+}
+
+// `a * b / d` rounded down, without overflowing, for `a < d`. Long division
+// of the 256-bit product, one bit of `b` at a time.
+pub(crate) fn mul_div(a: u128, b: u128, d: u128) -> u128 {
+    let (mut q, mut r) = (0, 0);
+
+    for bit in (0..128).rev() {
+        q <<= 1;
+        if r >= d - r {
+            r -= d - r;
+            q += 1;
+        } else {
+            r <<= 1;
+        }
+
+        if (b >> bit) & 1 == 1 {
+            if a >= d - r {
+                r = a - (d - r);
+                q += 1;
+            } else {
+                r += a;
+            }
+        }
+    }
+
+    q
+}
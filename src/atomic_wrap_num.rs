@@ -0,0 +1,220 @@
+use std::sync::atomic::{AtomicU8, AtomicU16, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+// Unsigned primitive with a matching std atomic type.
+pub trait AtomicUnsigned: UnsignedUnified {
+    type Atomic: Send + Sync;
+
+    fn new_atomic(value: Self) -> Self::Atomic;
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+    fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self,
+                        success: Ordering, failure: Ordering) -> Result<Self, Self>;
+    fn compare_exchange_weak(atomic: &Self::Atomic, current: Self, new: Self,
+                             success: Ordering, failure: Ordering) -> Result<Self, Self>;
+}
+
+macro_rules! impl_atomic_unsigned {
+    ($($t:ty => $atomic:ty),*) => {
+        $(
+            impl AtomicUnsigned for $t {
+                type Atomic = $atomic;
+
+                fn new_atomic(value: Self) -> Self::Atomic {
+                    <$atomic>::new(value)
+                }
+
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order)
+                }
+
+                fn compare_exchange(atomic: &Self::Atomic, current: Self, new: Self,
+                                    success: Ordering, failure: Ordering) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+
+                fn compare_exchange_weak(atomic: &Self::Atomic, current: Self, new: Self,
+                                         success: Ordering, failure: Ordering) -> Result<Self, Self> {
+                    atomic.compare_exchange_weak(current, new, success, failure)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_unsigned!(u8 => AtomicU8, u16 => AtomicU16, u32 => AtomicU32, u64 => AtomicU64,
+                      usize => AtomicUsize);
+
+// Atomic counter wrapping at an arbitrary modulus rather than the type width.
+// The wrap is fixed at construction; fetch_add and fetch_sub run a CAS loop,
+// so `value < wrap` holds for every value any thread can observe.
+pub struct AtomicWrapNum<T: AtomicUnsigned> {
+    value: T::Atomic,
+    wrap: T,
+}
+
+impl<T: AtomicUnsigned> AtomicWrapNum<T> {
+    pub fn new(value: T, wrap: T) -> AtomicWrapNum<T> {
+        assert!(wrap != T::zero(), "wrap must be nonzero");
+        assert!(value < wrap, "value must be less than wrap");
+
+        AtomicWrapNum {
+            value: T::new_atomic(value),
+            wrap,
+        }
+    }
+
+    pub fn get_wrap(&self) -> T {
+        self.wrap
+    }
+
+    pub fn load(&self, order: Ordering) -> WrapNum<T> {
+        WrapNum::new(T::load(&self.value, order), self.wrap)
+    }
+
+    pub fn store(&self, value: T, order: Ordering) {
+        assert!(value < self.wrap, "value must be less than wrap");
+
+        T::store(&self.value, value, order);
+    }
+
+    // Adds `delta` with wrap and returns the previous value.
+    pub fn fetch_add(&self, delta: T, order: Ordering) -> WrapNum<T> {
+        self.fetch_update(order, |num| num.add_with_carry(delta).0)
+    }
+
+    // Subtracts `delta` with wrap and returns the previous value.
+    pub fn fetch_sub(&self, delta: T, order: Ordering) -> WrapNum<T> {
+        let back = self.wrap - delta % self.wrap;
+
+        self.fetch_update(order, |num| num.add_with_carry(back).0)
+    }
+
+    pub fn compare_exchange(&self, current: T, new: T, success: Ordering,
+                            failure: Ordering) -> Result<WrapNum<T>, WrapNum<T>> {
+        assert!(new < self.wrap, "value must be less than wrap");
+
+        T::compare_exchange(&self.value, current, new, success, failure)
+            .map(|value| WrapNum::new(value, self.wrap))
+            .map_err(|value| WrapNum::new(value, self.wrap))
+    }
+
+    fn fetch_update<F: Fn(WrapNum<T>) -> WrapNum<T>>(&self, order: Ordering, f: F) -> WrapNum<T> {
+        let fetch_order = match order {
+            Ordering::Release => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Acquire,
+            order => order,
+        };
+        let mut current = T::load(&self.value, fetch_order);
+
+        loop {
+            let new = f(WrapNum::new(current, self.wrap)).get_value();
+
+            match T::compare_exchange_weak(&self.value, current, new, order, fetch_order) {
+                Ok(previous) => return WrapNum::new(previous, self.wrap),
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use super::*;
+
+    #[test]
+    fn fetch_add_wraps() {
+        let num = AtomicWrapNum::new(5u32, 7u32);
+
+        let previous = num.fetch_add(3, Ordering::SeqCst);
+
+        assert_eq!(previous.get_value(), 5);
+        assert_eq!(num.load(Ordering::SeqCst).get_value(), 1);
+    }
+
+    #[test]
+    fn fetch_add_near_type_max() {
+        let num = AtomicWrapNum::new(250u8, 251u8);
+
+        num.fetch_add(255, Ordering::Relaxed);
+
+        assert_eq!(num.load(Ordering::Relaxed).get_value(), 3);
+    }
+
+    #[test]
+    fn fetch_sub_wraps() {
+        let num = AtomicWrapNum::new(1u16, 7u16);
+
+        let previous = num.fetch_sub(3, Ordering::AcqRel);
+
+        assert_eq!(previous.get_value(), 1);
+        assert_eq!(num.load(Ordering::Acquire).get_value(), 5);
+
+        num.fetch_sub(14, Ordering::Release);
+        assert_eq!(num.load(Ordering::Acquire).get_value(), 5);
+    }
+
+    #[test]
+    fn store_and_compare_exchange() {
+        let num = AtomicWrapNum::new(0u64, 10u64);
+
+        num.store(4, Ordering::SeqCst);
+
+        assert_eq!(num.compare_exchange(3, 9, Ordering::SeqCst, Ordering::SeqCst),
+                   Err(WrapNum::new(4, 10)));
+        assert_eq!(num.compare_exchange(4, 9, Ordering::SeqCst, Ordering::SeqCst),
+                   Ok(WrapNum::new(4, 10)));
+        assert_eq!(num.load(Ordering::SeqCst).get_value(), 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn new_zero_wrap() {
+        let _ = AtomicWrapNum::new(0u32, 0u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "value must be less than wrap")]
+    fn store_out_of_range() {
+        let num = AtomicWrapNum::new(0usize, 10usize);
+
+        num.store(10, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn concurrent_fetch_add_hits_every_residue_uniformly() {
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 7_000;
+
+        let num = AtomicWrapNum::new(0usize, 7usize);
+
+        let counts = thread::scope(|scope| {
+            let handles: Vec<_> = (0..THREADS).map(|_| scope.spawn(|| {
+                let mut counts = [0usize; 7];
+
+                for _ in 0..PER_THREAD {
+                    let previous = num.fetch_add(1, Ordering::Relaxed).get_value();
+                    assert!(previous < 7);
+                    counts[previous] += 1;
+                }
+
+                counts
+            })).collect();
+
+            handles.into_iter().map(|handle| handle.join().unwrap()).fold([0usize; 7], |mut total, counts| {
+                for (total, count) in total.iter_mut().zip(counts) {
+                    *total += count;
+                }
+                total
+            })
+        });
+
+        assert_eq!(counts, [THREADS * PER_THREAD / 7; 7]);
+        assert_eq!(num.load(Ordering::Relaxed).get_value(), 0);
+    }
+}
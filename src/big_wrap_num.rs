@@ -0,0 +1,247 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
+use num::{BigUint, One};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct BigWrapNum {
+    value: BigUint,
+    wrap: BigUint,
+}
+
+// WrapNum counterpart for arbitrarily large moduli, backed by BigUint.
+// Operators take the right-hand side by reference, and the owned-lhs and
+// assign forms update the existing buffer instead of allocating a new one.
+// Unlike WrapNum's `-`, subtraction here is modular: big moduli are used for
+// ring arithmetic, where a panicking underflow is never what's wanted.
+// When operating with multiple BigWrapNums, the wrap value of the former is taken.
+impl BigWrapNum {
+    pub fn new(value: BigUint, wrap: BigUint) -> BigWrapNum {
+        BigWrapNum::try_new(value, wrap).expect("value must be less than a nonzero wrap")
+    }
+
+    // Returns None unless value < wrap.
+    pub fn try_new(value: BigUint, wrap: BigUint) -> Option<BigWrapNum> {
+        if value < wrap {
+            Some(BigWrapNum { value, wrap })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_value(&self) -> &BigUint {
+        &self.value
+    }
+
+    pub fn get_wrap(&self) -> &BigUint {
+        &self.wrap
+    }
+
+    pub fn into_value(self) -> BigUint {
+        self.value
+    }
+
+    pub fn pow(&self, exp: &BigUint) -> BigWrapNum {
+        BigWrapNum {
+            value: self.value.modpow(exp, &self.wrap),
+            wrap: self.wrap.clone(),
+        }
+    }
+
+    // Multiplicative inverse, if value and wrap are coprime.
+    pub fn mod_inverse(&self) -> Option<BigWrapNum> {
+        if self.wrap.is_one() {
+            return Some(self.clone());
+        }
+
+        self.value.modinv(&self.wrap).map(|value| BigWrapNum {
+            value,
+            wrap: self.wrap.clone(),
+        })
+    }
+
+    // Residue of `rhs` in this ring, borrowed when it's already reduced.
+    fn reduce_rhs<'a>(&self, rhs: &'a BigWrapNum) -> std::borrow::Cow<'a, BigUint> {
+        if rhs.value < self.wrap {
+            std::borrow::Cow::Borrowed(&rhs.value)
+        } else {
+            std::borrow::Cow::Owned(&rhs.value % &self.wrap)
+        }
+    }
+}
+
+impl AddAssign<&BigWrapNum> for BigWrapNum {
+    fn add_assign(&mut self, rhs: &BigWrapNum) {
+        let rhs = self.reduce_rhs(rhs);
+
+        self.value += &*rhs;
+        if self.value >= self.wrap {
+            self.value -= &self.wrap;
+        }
+    }
+}
+
+impl Add<&BigWrapNum> for BigWrapNum {
+    type Output = Self;
+
+    fn add(mut self, rhs: &BigWrapNum) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl Add<&BigWrapNum> for &BigWrapNum {
+    type Output = BigWrapNum;
+
+    fn add(self, rhs: &BigWrapNum) -> Self::Output {
+        self.clone() + rhs
+    }
+}
+
+impl SubAssign<&BigWrapNum> for BigWrapNum {
+    fn sub_assign(&mut self, rhs: &BigWrapNum) {
+        let rhs = self.reduce_rhs(rhs);
+
+        if self.value >= *rhs {
+            self.value -= &*rhs;
+        } else {
+            self.value += &self.wrap;
+            self.value -= &*rhs;
+        }
+    }
+}
+
+impl Sub<&BigWrapNum> for BigWrapNum {
+    type Output = Self;
+
+    fn sub(mut self, rhs: &BigWrapNum) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl Sub<&BigWrapNum> for &BigWrapNum {
+    type Output = BigWrapNum;
+
+    fn sub(self, rhs: &BigWrapNum) -> Self::Output {
+        self.clone() - rhs
+    }
+}
+
+impl MulAssign<&BigWrapNum> for BigWrapNum {
+    fn mul_assign(&mut self, rhs: &BigWrapNum) {
+        self.value *= &rhs.value;
+        self.value %= &self.wrap;
+    }
+}
+
+impl Mul<&BigWrapNum> for BigWrapNum {
+    type Output = Self;
+
+    fn mul(mut self, rhs: &BigWrapNum) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl Mul<&BigWrapNum> for &BigWrapNum {
+    type Output = BigWrapNum;
+
+    fn mul(self, rhs: &BigWrapNum) -> Self::Output {
+        self.clone() * rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::Zero;
+
+    // 2^256 - 189, the largest 256-bit prime.
+    fn p256() -> BigUint {
+        (BigUint::one() << 256u32) - 189u32
+    }
+
+    fn big(x: &str) -> BigUint {
+        BigUint::parse_bytes(x.as_bytes(), 16).unwrap()
+    }
+
+    fn a() -> BigWrapNum {
+        BigWrapNum::new(big("f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899aabbccddeeff"), p256())
+    }
+
+    fn b() -> BigWrapNum {
+        BigWrapNum::new(big("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe00"), p256())
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(BigWrapNum::try_new(p256(), p256()).is_none());
+        assert!(BigWrapNum::try_new(BigUint::zero(), BigUint::zero()).is_none());
+        assert!(BigWrapNum::try_new(p256() - 1u32, p256()).is_some());
+    }
+
+    #[test]
+    fn add_256_bit() {
+        let sum = &a() + &b();
+
+        assert_eq!(sum.get_value(), &((a().into_value() + b().into_value()) % p256()));
+        assert_eq!(sum.get_wrap(), &p256());
+    }
+
+    #[test]
+    fn sub_256_bit() {
+        let mut diff = a();
+
+        diff -= &b();
+
+        assert_eq!(diff.get_value(), &((a().into_value() + p256() - b().into_value()) % p256()));
+        assert_eq!((&diff + &b()), a());
+    }
+
+    #[test]
+    fn mul_256_bit() {
+        let product = a() * &b();
+
+        assert_eq!(product.get_value(), &((a().into_value() * b().into_value()) % p256()));
+    }
+
+    #[test]
+    fn pow_matches_modpow() {
+        let exp = big("123456789abcdef0123456789abcdef");
+
+        let power = a().pow(&exp);
+
+        assert_eq!(power.get_value(), &a().get_value().modpow(&exp, &p256()));
+    }
+
+    #[test]
+    fn fermat_little_theorem() {
+        let exp = p256() - 1u32;
+
+        assert!(a().pow(&exp).get_value().is_one());
+        assert!(b().pow(&exp).get_value().is_one());
+    }
+
+    #[test]
+    fn mod_inverse() {
+        let inverse = a().mod_inverse().unwrap();
+
+        assert!((a() * &inverse).get_value().is_one());
+        assert_eq!(inverse.get_value(), &a().get_value().modpow(&(p256() - 2u32), &p256()));
+    }
+
+    #[test]
+    fn mod_inverse_not_coprime() {
+        let num = BigWrapNum::new(BigUint::from(6u32), BigUint::from(9u32));
+
+        assert!(num.mod_inverse().is_none());
+    }
+
+    #[test]
+    fn wrap_one() {
+        let num = BigWrapNum::new(BigUint::zero(), BigUint::one());
+
+        assert!((&num * &num).get_value().is_zero());
+        assert!(num.pow(&BigUint::zero()).get_value().is_zero());
+        assert_eq!(num.mod_inverse(), Some(num));
+    }
+}
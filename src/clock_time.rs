@@ -0,0 +1,167 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::time::Duration;
+use crate::wrap_num::WrapNum;
+
+const SECONDS_PER_DAY: u32 = 86_400;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ClockTime(WrapNum<u32>);
+
+// Time of day as seconds since midnight, wrapping across midnight.
+// Resolution is one second: sub-second parts of Durations are truncated.
+impl ClockTime {
+    // Returns None unless hours < 24, minutes < 60 and seconds < 60.
+    pub fn from_hms(hours: u32, minutes: u32, seconds: u32) -> Option<ClockTime> {
+        if hours >= 24 || minutes >= 60 || seconds >= 60 {
+            return None;
+        }
+
+        Some(ClockTime(WrapNum::new(hours * 3600 + minutes * 60 + seconds, SECONDS_PER_DAY)))
+    }
+
+    pub fn midnight() -> ClockTime {
+        ClockTime(WrapNum::new(0, SECONDS_PER_DAY))
+    }
+
+    pub fn hours(self) -> u32 {
+        self.0.get_value() / 3600
+    }
+
+    pub fn minutes(self) -> u32 {
+        self.0.get_value() / 60 % 60
+    }
+
+    pub fn seconds(self) -> u32 {
+        self.0.get_value() % 60
+    }
+
+    pub fn seconds_since_midnight(self) -> u32 {
+        self.0.get_value()
+    }
+
+    // Signed number of seconds from `self` to the nearest occurrence of
+    // `other`, in [-12h, 12h). Crossing midnight takes the short way round,
+    // so 23:00 until 01:00 is 7200 and 01:00 until 23:00 is -7200.
+    pub fn duration_until(self, other: ClockTime) -> i64 {
+        let (forward, _) = other.0.add_with_carry(SECONDS_PER_DAY - self.0.get_value());
+        let forward = forward.get_value() as i64;
+
+        if forward >= (SECONDS_PER_DAY / 2) as i64 {
+            forward - SECONDS_PER_DAY as i64
+        } else {
+            forward
+        }
+    }
+}
+
+// Whole seconds of `duration`, reduced to less than a day.
+fn day_seconds(duration: Duration) -> u32 {
+    (duration.as_secs() % SECONDS_PER_DAY as u64) as u32
+}
+
+impl Add<Duration> for ClockTime {
+    type Output = Self;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        ClockTime(self.0.add_with_carry(day_seconds(rhs)).0)
+    }
+}
+
+impl AddAssign<Duration> for ClockTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for ClockTime {
+    type Output = Self;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        let back = (SECONDS_PER_DAY - day_seconds(rhs)) % SECONDS_PER_DAY;
+
+        ClockTime(self.0.add_with_carry(back).0)
+    }
+}
+
+impl SubAssign<Duration> for ClockTime {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hms_accessors() {
+        let time = ClockTime::from_hms(13, 45, 30).unwrap();
+
+        assert_eq!(time.hours(), 13);
+        assert_eq!(time.minutes(), 45);
+        assert_eq!(time.seconds(), 30);
+        assert_eq!(time.seconds_since_midnight(), 49530);
+    }
+
+    #[test]
+    fn from_hms_invalid() {
+        assert_eq!(ClockTime::from_hms(24, 0, 0), None);
+        assert_eq!(ClockTime::from_hms(0, 60, 0), None);
+        assert_eq!(ClockTime::from_hms(0, 0, 60), None);
+    }
+
+    #[test]
+    fn add_across_midnight() {
+        let time = ClockTime::from_hms(23, 59, 30).unwrap();
+
+        let time = time + Duration::from_secs(45);
+
+        assert_eq!(time, ClockTime::from_hms(0, 0, 15).unwrap());
+    }
+
+    #[test]
+    fn add_multiple_days_truncates_subsec() {
+        let mut time = ClockTime::from_hms(12, 0, 0).unwrap();
+
+        time += Duration::from_millis(3 * 86_400_000 + 1_999);
+
+        assert_eq!(time, ClockTime::from_hms(12, 0, 1).unwrap());
+    }
+
+    #[test]
+    fn sub_across_midnight() {
+        let time = ClockTime::from_hms(0, 0, 15).unwrap();
+
+        let time = time - Duration::from_secs(45);
+
+        assert_eq!(time, ClockTime::from_hms(23, 59, 30).unwrap());
+    }
+
+    #[test]
+    fn sub_whole_day() {
+        let mut time = ClockTime::from_hms(8, 30, 0).unwrap();
+
+        time -= Duration::from_secs(86_400);
+
+        assert_eq!(time, ClockTime::from_hms(8, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn duration_until_across_midnight() {
+        let late = ClockTime::from_hms(23, 0, 0).unwrap();
+        let early = ClockTime::from_hms(1, 0, 0).unwrap();
+
+        assert_eq!(late.duration_until(early), 7200);
+        assert_eq!(early.duration_until(late), -7200);
+    }
+
+    #[test]
+    fn duration_until_same_day() {
+        let time1 = ClockTime::from_hms(9, 0, 0).unwrap();
+        let time2 = ClockTime::from_hms(9, 30, 15).unwrap();
+
+        assert_eq!(time1.duration_until(time2), 1815);
+        assert_eq!(time1.duration_until(time1), 0);
+        assert_eq!(ClockTime::midnight().duration_until(ClockTime::from_hms(12, 0, 0).unwrap()), -43200);
+    }
+}
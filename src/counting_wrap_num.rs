@@ -0,0 +1,206 @@
+use std::ops::{AddAssign, SubAssign};
+use num::ToPrimitive;
+use crate::wrap_num::{WrapNum, UnsignedUnified, cast_rhs};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct CountingWrapNum<T: UnsignedUnified> {
+    num: WrapNum<T>,
+    laps: u64,
+}
+
+// WrapNum that also counts how many times it crossed the wrap boundary, so
+// both the wrapped value and the true total are available.
+// Moving backwards past 0 wraps the value as usual and takes laps off the
+// counter, saturating at zero laps: `total()` never goes negative, it only
+// stops being exact once a subtraction took it below zero. Likewise the lap
+// count saturates at u64::MAX, which only a WrapNum<u128> with a tiny wrap
+// can reach.
+impl<T: UnsignedUnified> CountingWrapNum<T> {
+    pub fn new(value: T, wrap: T) -> CountingWrapNum<T> {
+        CountingWrapNum {
+            num: WrapNum::new(value, wrap),
+            laps: 0,
+        }
+    }
+
+    pub fn get_value(self) -> T {
+        self.num.get_value()
+    }
+
+    pub fn get_wrap(self) -> T {
+        self.num.get_wrap()
+    }
+
+    pub fn laps(self) -> u64 {
+        self.laps
+    }
+
+    pub fn reset_laps(&mut self) {
+        self.laps = 0;
+    }
+
+    // laps * wrap + value, saturating at u128::MAX.
+    pub fn total(self) -> u128 {
+        self.checked_total().unwrap_or(u128::MAX)
+    }
+
+    // laps * wrap + value, or None if it doesn't fit in a u128.
+    pub fn checked_total(self) -> Option<u128> {
+        (self.laps as u128)
+            .checked_mul(self.get_wrap().to_u128().unwrap())?
+            .checked_add(self.get_value().to_u128().unwrap())
+    }
+
+    pub fn increment(&mut self) {
+        self.add(T::one());
+    }
+
+    pub fn decrement(&mut self) {
+        self.sub(T::one());
+    }
+
+    pub fn add(&mut self, delta: T) {
+        let (num, laps) = self.num.add_with_carry(delta);
+
+        self.num = num;
+        self.laps = self.laps.saturating_add(laps.to_u64().unwrap_or(u64::MAX));
+    }
+
+    pub fn sub(&mut self, delta: T) {
+        let wrap = self.get_wrap();
+        let value = self.get_value();
+        let rhs = delta % wrap;
+        let mut laps = (delta / wrap).to_u64().unwrap_or(u64::MAX);
+
+        self.num = if rhs > value {
+            laps = laps.saturating_add(1);
+            WrapNum::new(wrap - (rhs - value), wrap)
+        } else {
+            self.num - rhs
+        };
+        self.laps = self.laps.saturating_sub(laps);
+    }
+}
+
+impl<T: UnsignedUnified> From<CountingWrapNum<T>> for WrapNum<T> {
+    fn from(num: CountingWrapNum<T>) -> Self {
+        num.num
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> AddAssign<U> for CountingWrapNum<T> {
+    fn add_assign(&mut self, rhs: U) {
+        self.add(cast_rhs(rhs));
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> SubAssign<U> for CountingWrapNum<T> {
+    fn sub_assign(&mut self, rhs: U) {
+        self.sub(cast_rhs(rhs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_counts_laps() {
+        let mut num = CountingWrapNum::new(0u8, 3u8);
+
+        for _ in 0..7 {
+            num.increment();
+        }
+
+        assert_eq!(num.get_value(), 1);
+        assert_eq!(num.laps(), 2);
+        assert_eq!(num.total(), 7);
+    }
+
+    #[test]
+    fn add_large_delta_counts_multiple_laps() {
+        let mut num = CountingWrapNum::new(5u32, 10u32);
+
+        num += 1234u32;
+
+        assert_eq!(num.get_value(), 9);
+        assert_eq!(num.laps(), 123);
+    }
+
+    #[test]
+    fn total_matches_accumulated() {
+        let mut num = CountingWrapNum::new(0u16, 1000u16);
+        let mut expected = 0u128;
+
+        for i in 0..10_000u16 {
+            let delta = i.wrapping_mul(7919) % 5000;
+
+            num += delta;
+            expected += delta as u128;
+        }
+
+        assert_eq!(num.total(), expected);
+    }
+
+    #[test]
+    fn sub_takes_laps_off() {
+        let mut num = CountingWrapNum::new(2u32, 10u32);
+
+        num += 25u32;
+        num -= 13u32;
+
+        assert_eq!(num.get_value(), 4);
+        assert_eq!(num.laps(), 1);
+        assert_eq!(num.total(), 14);
+    }
+
+    #[test]
+    fn sub_saturates_laps() {
+        let mut num = CountingWrapNum::new(2u32, 10u32);
+
+        num.decrement();
+        num.decrement();
+        num.decrement();
+
+        assert_eq!(num.get_value(), 9);
+        assert_eq!(num.laps(), 0);
+    }
+
+    #[test]
+    fn reset_laps() {
+        let mut num = CountingWrapNum::new(0u32, 4u32);
+
+        num += 9u32;
+        num.reset_laps();
+
+        assert_eq!(num.laps(), 0);
+        assert_eq!(num.total(), 1);
+        assert_eq!(WrapNum::from(num), WrapNum::new(1, 4));
+    }
+
+    #[test]
+    fn laps_saturate_for_u128() {
+        let mut num = CountingWrapNum::new(0u128, 1u128);
+
+        num += u128::MAX;
+        assert_eq!(num.laps(), u64::MAX);
+        num += 5u8;
+        assert_eq!(num.laps(), u64::MAX);
+
+        num -= u128::MAX;
+        assert_eq!(num.laps(), 0);
+    }
+
+    #[test]
+    fn total_overflow_u128() {
+        let mut num = CountingWrapNum::new(0u128, u128::MAX);
+
+        num += u128::MAX - 1;
+        num += 1u8;
+        assert_eq!((num.laps(), num.checked_total()), (1, Some(u128::MAX)));
+
+        num += 1u8;
+        assert_eq!(num.checked_total(), None);
+        assert_eq!(num.total(), u128::MAX);
+    }
+}
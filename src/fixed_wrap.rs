@@ -0,0 +1,518 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Rem, RemAssign};
+use std::num::ParseIntError;
+use num::{Bounded, FromPrimitive, Integer, Num, NumCast, ToPrimitive, Zero, One};
+use crate::arith::mul_mod;
+use crate::wrap_num::{WrapNum, WrapInteger, UnsignedUnified};
+
+// WrapNum whose wrap is the type parameter `W` instead of a field, so a value
+// takes exactly as much space as `T` and values of different wraps are
+// different types. Operators behave like WrapNum's, with `/` and `%`
+// truncating the value.
+// The layout is exactly T's, so slices of raw values can be viewed as slices
+// of FixedWraps once every value has been checked against W.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct FixedWrap<T: UnsignedUnified, const W: usize> {
+    value: T,
+}
+
+// Name used where the const wrap is the point, e.g. `ConstWrapNum<u32, 360>`.
+pub type ConstWrapNum<T, const W: usize> = FixedWrap<T, W>;
+
+impl<T: UnsignedUnified, const W: usize> FixedWrap<T, W> {
+    // The wrap, readable in const contexts, e.g. to size `[T; MODULUS]`.
+    pub const MODULUS: usize = W;
+
+    pub const fn modulus() -> usize {
+        W
+    }
+
+    pub fn new(value: T) -> FixedWrap<T, W> {
+        FixedWrap::try_new(value).expect("value must be less than W")
+    }
+
+    // Returns None unless value < W. Panics if W is 0 or doesn't fit in T.
+    pub fn try_new(value: T) -> Option<FixedWrap<T, W>> {
+        if value < Self::wrap() {
+            Some(FixedWrap { value })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_value(self) -> T {
+        self.value
+    }
+
+    pub fn get_wrap(self) -> T {
+        Self::wrap()
+    }
+
+    pub fn to_wrap_num(self) -> WrapNum<T> {
+        WrapNum::new(self.value, Self::wrap())
+    }
+
+    // Views `values` as FixedWraps without copying, or returns None if any
+    // of them is not below W.
+    pub fn try_cast_slice(values: &[T]) -> Option<&[FixedWrap<T, W>]> {
+        if !values.iter().all(|&value| value < Self::wrap()) {
+            return None;
+        }
+
+        // SAFETY: FixedWrap is repr(transparent) over T, and every value
+        // has just been checked to satisfy value < W.
+        Some(unsafe { std::slice::from_raw_parts(values.as_ptr().cast(), values.len()) })
+    }
+
+    // The raw values behind a slice of FixedWraps.
+    pub fn as_value_slice(nums: &[FixedWrap<T, W>]) -> &[T] {
+        // SAFETY: FixedWrap is repr(transparent) over T.
+        unsafe { std::slice::from_raw_parts(nums.as_ptr().cast(), nums.len()) }
+    }
+
+    fn wrap() -> T {
+        assert!(W > 0, "wrap must be nonzero");
+
+        NumCast::from(W).expect("W must fit in T")
+    }
+
+    fn from_wrap_num(num: WrapNum<T>) -> FixedWrap<T, W> {
+        FixedWrap { value: num.get_value() }
+    }
+}
+
+// Zero, which every ring has.
+impl<T: UnsignedUnified, const W: usize> Default for FixedWrap<T, W> {
+    fn default() -> Self {
+        FixedWrap::new(T::zero())
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize> Zero for FixedWrap<T, W> {
+    fn zero() -> Self {
+        FixedWrap::new(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == T::zero()
+    }
+}
+
+// With W == 1 one is the same element as zero.
+impl<T: UnsignedUnified, const W: usize> One for FixedWrap<T, W> {
+    fn one() -> Self {
+        FixedWrap::new(T::one() % Self::wrap())
+    }
+}
+
+// 0 and W - 1, which are the same element when W == 1.
+impl<T: UnsignedUnified, const W: usize> Bounded for FixedWrap<T, W> {
+    fn min_value() -> Self {
+        FixedWrap::new(T::zero())
+    }
+
+    fn max_value() -> Self {
+        FixedWrap::new(Self::wrap() - T::one())
+    }
+}
+
+// Reduces like WrapNum's `from_*_reduced` constructors, so only non-finite
+// floats give None.
+impl<T: UnsignedUnified, const W: usize> FromPrimitive for FixedWrap<T, W> {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(FixedWrap::from_wrap_num(WrapNum::from_i64_reduced(n, Self::wrap())))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(FixedWrap::from_wrap_num(WrapNum::from_u64_reduced(n, Self::wrap())))
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        WrapNum::from_f64_reduced(n, Self::wrap()).map(FixedWrap::from_wrap_num)
+    }
+}
+
+// `from_str_radix` reduces the parsed integer modulo W rather than rejecting
+// it, so any integer that fits in a u128 maps to its residue.
+impl<T: UnsignedUnified, const W: usize> Num for FixedWrap<T, W> {
+    type FromStrRadixErr = ParseIntError;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let value = u128::from_str_radix(str, radix)? % Self::wrap().to_u128().unwrap();
+
+        Ok(FixedWrap::new(NumCast::from(value).unwrap()))
+    }
+}
+
+// Integer operations on the residues, with T's own semantics for division
+// by zero. Every result is at most the larger operand and so stays below W,
+// except `lcm`, which is reduced modulo W. WrapNum has no impl, as it can't
+// provide the Zero and One that Num requires.
+impl<T: UnsignedUnified + Integer, const W: usize> Integer for FixedWrap<T, W> {
+    fn div_floor(&self, other: &Self) -> Self {
+        FixedWrap { value: self.value.div_floor(&other.value) }
+    }
+
+    fn mod_floor(&self, other: &Self) -> Self {
+        FixedWrap { value: self.value.mod_floor(&other.value) }
+    }
+
+    fn gcd(&self, other: &Self) -> Self {
+        FixedWrap { value: self.value.gcd(&other.value) }
+    }
+
+    fn lcm(&self, other: &Self) -> Self {
+        if self.value.is_zero() || other.value.is_zero() {
+            return FixedWrap::zero();
+        }
+
+        let a = (self.value / self.value.gcd(&other.value)).to_u128().unwrap();
+        let lcm = mul_mod(a, other.value.to_u128().unwrap(), Self::wrap().to_u128().unwrap());
+
+        FixedWrap { value: NumCast::from(lcm).unwrap() }
+    }
+
+    fn is_multiple_of(&self, other: &Self) -> bool {
+        self.value.is_multiple_of(&other.value)
+    }
+
+    fn is_even(&self) -> bool {
+        self.value.is_even()
+    }
+
+    fn is_odd(&self) -> bool {
+        self.value.is_odd()
+    }
+
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (self.div_floor(other), self.mod_floor(other))
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize> From<FixedWrap<T, W>> for WrapNum<T> {
+    fn from(num: FixedWrap<T, W>) -> Self {
+        num.to_wrap_num()
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize> WrapInteger<T> for FixedWrap<T, W> {
+    fn get_value(self) -> T {
+        self.value
+    }
+
+    fn get_wrap(self) -> T {
+        Self::wrap()
+    }
+
+    fn successor(self) -> Self {
+        FixedWrap::from_wrap_num(self.to_wrap_num().successor())
+    }
+
+    fn wrapping_sub(self, rhs: T) -> Self {
+        FixedWrap::from_wrap_num(self.to_wrap_num().wrapping_sub(rhs))
+    }
+}
+
+// Converts the value, so a FixedWrap can be the right-hand side of any
+// wrapping operator.
+impl<T: UnsignedUnified, const W: usize> ToPrimitive for FixedWrap<T, W> {
+    fn to_i64(&self) -> Option<i64> {
+        self.value.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> Add<U> for FixedWrap<T, W> {
+    type Output = Self;
+
+    fn add(self, rhs: U) -> Self::Output {
+        FixedWrap::from_wrap_num(self.to_wrap_num() + rhs)
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> AddAssign<U> for FixedWrap<T, W> {
+    fn add_assign(&mut self, rhs: U) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> Sub<U> for FixedWrap<T, W> {
+    type Output = Self;
+
+    fn sub(self, rhs: U) -> Self::Output {
+        FixedWrap::from_wrap_num(self.to_wrap_num() - rhs)
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> SubAssign<U> for FixedWrap<T, W> {
+    fn sub_assign(&mut self, rhs: U) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> Mul<U> for FixedWrap<T, W> {
+    type Output = Self;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        FixedWrap::from_wrap_num(self.to_wrap_num() * rhs)
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> MulAssign<U> for FixedWrap<T, W> {
+    fn mul_assign(&mut self, rhs: U) {
+        *self = *self * rhs;
+    }
+}
+
+// Truncating division of the value, as for the underlying integer.
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> Div<U> for FixedWrap<T, W> {
+    type Output = Self;
+
+    fn div(self, rhs: U) -> Self::Output {
+        FixedWrap::from_wrap_num(self.to_wrap_num() / rhs)
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> DivAssign<U> for FixedWrap<T, W> {
+    fn div_assign(&mut self, rhs: U) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> Rem<U> for FixedWrap<T, W> {
+    type Output = Self;
+
+    fn rem(self, rhs: U) -> Self::Output {
+        FixedWrap::from_wrap_num(self.to_wrap_num() % rhs)
+    }
+}
+
+impl<T: UnsignedUnified, const W: usize, U: ToPrimitive> RemAssign<U> for FixedWrap<T, W> {
+    fn rem_assign(&mut self, rhs: U) {
+        *self = *self % rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_is_value_only() {
+        assert_eq!(std::mem::size_of::<FixedWrap<u32, 360>>(), 4);
+        assert_eq!(std::mem::size_of::<[FixedWrap<u16, 7>; 16]>(), 32);
+    }
+
+    #[test]
+    fn add_wrap() {
+        let mut num = FixedWrap::<u32, 6>::new(4);
+
+        num += 5u32;
+
+        assert_eq!(num.get_value(), 3);
+        assert_eq!(num.get_wrap(), 6);
+    }
+
+    #[test]
+    fn ops_match_wrap_num() {
+        for a in 0..11u8 {
+            for b in 0..11u8 {
+                let fixed = FixedWrap::<u8, 11>::new(a);
+                let num = WrapNum::new(a, 11);
+
+                assert_eq!(WrapNum::from(fixed + b), num + b);
+                assert_eq!(WrapNum::from(fixed * b), num * b);
+                assert_eq!(num + fixed, num + a);
+            }
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(FixedWrap::<u64, 10>::try_new(9).map(FixedWrap::get_value), Some(9));
+        assert_eq!(FixedWrap::<u64, 10>::try_new(10), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "W must fit in T")]
+    fn wrap_too_large_for_type() {
+        let _ = FixedWrap::<u8, 256>::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn zero_wrap() {
+        let _ = FixedWrap::<u8, 0>::new(0);
+    }
+
+    #[test]
+    fn try_cast_slice_valid() {
+        let raw = [0u32, 90, 180, 359];
+
+        let angles = ConstWrapNum::<u32, 360>::try_cast_slice(&raw).unwrap();
+
+        assert_eq!(angles.len(), 4);
+        assert_eq!(angles[2], FixedWrap::new(180));
+        assert_eq!((angles[3] + 1u32).get_value(), 0);
+        assert_eq!(FixedWrap::as_value_slice(angles), &raw);
+    }
+
+    #[test]
+    fn try_cast_slice_invalid() {
+        let raw = [0u32, 90, 360, 10];
+
+        assert!(ConstWrapNum::<u32, 360>::try_cast_slice(&raw).is_none());
+        assert!(ConstWrapNum::<u32, 360>::try_cast_slice(&[]).is_some());
+    }
+
+    #[test]
+    fn derive_default() {
+        #[derive(Default)]
+        struct Compass {
+            heading: ConstWrapNum<u16, 360>,
+            turns: u32,
+        }
+
+        let compass = Compass::default();
+
+        assert_eq!(compass.heading.get_value(), 0);
+        assert_eq!(compass.heading.get_wrap(), 360);
+        assert_eq!(compass.turns, 0);
+    }
+
+    #[test]
+    fn modulus_in_const_context() {
+        const SLOTS: usize = FixedWrap::<u32, 8>::MODULUS;
+        const fn slots_of<const W: usize>() -> usize {
+            FixedWrap::<u32, W>::modulus()
+        }
+
+        let table = [0u8; FixedWrap::<u32, 8>::MODULUS];
+
+        assert_eq!(SLOTS, 8);
+        assert_eq!(table.len(), 8);
+        assert_eq!([(); slots_of::<5>()].len(), 5);
+    }
+
+    fn sum<N: Zero + std::ops::Add<Output = N>>(items: impl IntoIterator<Item = N>) -> N {
+        items.into_iter().fold(N::zero(), |acc, item| acc + item)
+    }
+
+    #[test]
+    fn generic_sum() {
+        let items = [3u32, 4, 5].map(FixedWrap::<u32, 7>::new);
+
+        assert_eq!(sum(items), FixedWrap::new(5));
+        assert_eq!(sum(Vec::<FixedWrap<u32, 7>>::new()), FixedWrap::zero());
+    }
+
+    #[test]
+    fn zero_and_one() {
+        assert!(FixedWrap::<u8, 6>::zero().is_zero());
+        assert_eq!(FixedWrap::<u8, 6>::one().get_value(), 1);
+        assert!(FixedWrap::<u8, 6>::one().is_one());
+        assert_eq!(FixedWrap::<u8, 6>::one() * FixedWrap::<u8, 6>::new(5), FixedWrap::new(5));
+        assert_eq!(FixedWrap::<u8, 1>::one(), FixedWrap::<u8, 1>::zero());
+    }
+
+    // Horner's rule, coefficients from the highest degree down.
+    fn eval_poly<N: Num + Copy>(coefficients: &[N], x: N) -> N {
+        coefficients.iter().fold(N::zero(), |acc, &c| acc * x + c)
+    }
+
+    #[test]
+    fn num_polynomial() {
+        type P = ConstWrapNum<u64, 1_000_000_007>;
+        let coefficients = [3u64, 0, 999_999_999, 42].map(P::new);
+        let x = P::new(123_456_789);
+
+        let expected = [3u128, 0, 999_999_999, 42].iter()
+            .fold(0u128, |acc, &c| (acc * 123_456_789 + c) % 1_000_000_007);
+
+        assert_eq!(eval_poly(&coefficients, x).get_value() as u128, expected);
+    }
+
+    #[test]
+    fn from_str_radix() {
+        type N = ConstWrapNum<u8, 10>;
+
+        assert_eq!(N::from_str_radix("7", 10), Ok(N::new(7)));
+        assert_eq!(N::from_str_radix("ff", 16), Ok(N::new(5)));
+        assert_eq!(N::from_str_radix("1011", 2), Ok(N::new(1)));
+        assert!(N::from_str_radix("12z", 10).is_err());
+        assert!(N::from_str_radix("", 10).is_err());
+    }
+
+    #[test]
+    fn div_and_rem() {
+        let num = FixedWrap::<u32, 100>::new(47);
+
+        assert_eq!((num / 5u32).get_value(), 9);
+        assert_eq!((num % 5u32).get_value(), 2);
+        assert_eq!((num / 5u32) * 5u32 + (num % 5u32), num);
+    }
+
+    #[test]
+    fn div_matches_wrap_num() {
+        let fixed = FixedWrap::<u32, 10>::new(7);
+        let num = WrapNum::new(7u32, 10u32);
+
+        assert_eq!((fixed / 3u32).get_value(), 2);
+        assert_eq!(WrapNum::from(fixed / 3u32), num / 3u32);
+        assert_eq!(WrapNum::from(fixed % 3u32), num % 3u32);
+    }
+
+    #[test]
+    fn bounded() {
+        assert_eq!(FixedWrap::<u8, 6>::min_value().get_value(), 0);
+        assert_eq!(FixedWrap::<u8, 6>::max_value().get_value(), 5);
+        assert_eq!(FixedWrap::<u8, 1>::min_value(), FixedWrap::<u8, 1>::max_value());
+        assert_eq!(FixedWrap::<u8, 255>::max_value().get_value(), 254);
+    }
+
+    #[test]
+    fn from_primitive() {
+        type N = FixedWrap<u16, 360>;
+
+        assert_eq!(N::from_i64(-90), Some(N::new(270)));
+        assert_eq!(N::from_u64(725), Some(N::new(5)));
+        assert_eq!(N::from_i32(-1), Some(N::new(359)));
+        assert_eq!(N::from_f64(-0.25), Some(N::new(359)));
+        assert_eq!(N::from_f64(f64::NAN), None);
+    }
+
+    fn reduce_fraction<N: Integer + Copy>(numerator: N, denominator: N) -> (N, N) {
+        let gcd = numerator.gcd(&denominator);
+
+        (numerator.div_rem(&gcd).0, denominator.div_rem(&gcd).0)
+    }
+
+    fn count_even<N: Integer>(items: impl IntoIterator<Item = N>) -> usize {
+        items.into_iter().filter(|item| item.is_even()).count()
+    }
+
+    #[test]
+    fn integer_generic() {
+        type F = FixedWrap<u32, 100>;
+
+        assert_eq!(reduce_fraction(F::new(42), F::new(56)), (F::new(3), F::new(4)));
+        assert_eq!(reduce_fraction(42u32, 56u32), (3, 4));
+        assert_eq!(count_even((0..10).map(F::new)), 5);
+        assert!(F::new(7).is_odd());
+        assert_eq!(F::new(47).div_rem(&F::new(5)), (F::new(9), F::new(2)));
+        assert!(F::new(45).is_multiple_of(&F::new(9)));
+    }
+
+    #[test]
+    fn integer_lcm_reduced() {
+        type F = FixedWrap<u8, 100>;
+
+        assert_eq!(F::new(4).lcm(&F::new(6)), F::new(12));
+        assert_eq!(F::new(30).lcm(&F::new(45)), F::new(90));
+        assert_eq!(F::new(99).lcm(&F::new(98)), F::new((99 * 98 % 100) as u8));
+        assert_eq!(F::new(0).lcm(&F::new(6)), F::new(0));
+    }
+}
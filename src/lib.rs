@@ -1,3 +1,41 @@
 pub mod wrap_num;
+pub mod signed_wrap_num;
+pub mod wrap_float;
+pub mod angle;
+pub mod clock_time;
+pub mod odometer;
+pub mod counting_wrap_num;
+pub mod observed_wrap_num;
+pub mod atomic_wrap_num;
+pub mod modulus;
+pub mod wrap_vec;
+pub mod big_wrap_num;
+pub mod montgomery_wrap_num;
+pub mod fixed_wrap;
+pub mod pow2_wrap_num;
+pub mod ring_cursor;
+pub mod wrap_counter;
+pub mod seq_wrap;
+pub mod prelude;
+mod arith;
+#[cfg(test)]
+mod test_rng;
 
-pub use wrap_num::WrapNum;
+pub use wrap_num::{WrapNum, WrapInteger, DefaultWithWrap, RingIter, ParseWrapNumError, WrapError};
+pub use signed_wrap_num::SignedWrapNum;
+pub use wrap_float::WrapFloat;
+pub use angle::Angle;
+pub use clock_time::ClockTime;
+pub use odometer::Odometer;
+pub use counting_wrap_num::CountingWrapNum;
+pub use observed_wrap_num::{ObservedWrapNum, WrapEvent};
+pub use atomic_wrap_num::AtomicWrapNum;
+pub use modulus::{Modulus, WrapVal};
+pub use wrap_vec::WrapVec;
+pub use big_wrap_num::BigWrapNum;
+pub use montgomery_wrap_num::MontgomeryWrapNum;
+pub use fixed_wrap::{FixedWrap, ConstWrapNum};
+pub use pow2_wrap_num::Pow2WrapNum;
+pub use ring_cursor::RingCursor;
+pub use wrap_counter::WrapCounter;
+pub use seq_wrap::SeqWrap;
@@ -0,0 +1,251 @@
+use num::NumCast;
+use crate::arith::{add_mod, sub_mod, mul_hi, mul_mod};
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+// A wrap shared by many values. Arithmetic lives here, on values that store
+// nothing but their residue, so large collections don't repeat the wrap.
+// The Barrett reciprocal of the wrap is computed once in `new`, so reducing
+// needs a multiply and at most a couple of subtractions, never a division.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Modulus<T: UnsignedUnified> {
+    wrap: T,
+    // floor((2^128 - 1) / wrap).
+    reciprocal: u128,
+}
+
+// Residue that is only meaningful together with the Modulus that made it.
+// Mixing values from different moduli is not detected.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct WrapVal<T>(T);
+
+impl<T: Copy> WrapVal<T> {
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T: UnsignedUnified> Modulus<T> {
+    pub fn new(wrap: T) -> Modulus<T> {
+        assert!(wrap != T::zero(), "wrap must be nonzero");
+
+        Modulus {
+            wrap,
+            reciprocal: u128::MAX / wrap.to_u128().unwrap(),
+        }
+    }
+
+    pub fn get_wrap(self) -> T {
+        self.wrap
+    }
+
+    // Strict like `WrapNum::new`: panics unless value < wrap.
+    pub fn val(&self, value: T) -> WrapVal<T> {
+        assert!(value < self.wrap, "value must be less than wrap");
+
+        WrapVal(value)
+    }
+
+    // `x % wrap`, computed with the precomputed reciprocal.
+    pub fn reduce(&self, x: u128) -> T {
+        let n = self.wrap.to_u128().unwrap();
+        let q = mul_hi(x, self.reciprocal);
+        let mut r = x - q * n;
+
+        // q undershoots the true quotient by at most 2.
+        while r >= n {
+            r -= n;
+        }
+
+        NumCast::from(r).unwrap()
+    }
+
+    // WrapNum holding `value` reduced into this modulus.
+    pub fn wrapnum(&self, value: T) -> WrapNum<T> {
+        WrapNum::new(self.reduce(value.to_u128().unwrap()), self.wrap)
+    }
+
+    // Both operands are already below the wrap, so add, sub and neg only
+    // compare and subtract.
+    pub fn add(&self, a: WrapVal<T>, b: WrapVal<T>) -> WrapVal<T> {
+        self.residue(add_mod(a.0.to_u128().unwrap(), b.0.to_u128().unwrap(), self.wrap.to_u128().unwrap()))
+    }
+
+    // Wraps below 0 instead of panicking.
+    pub fn sub(&self, a: WrapVal<T>, b: WrapVal<T>) -> WrapVal<T> {
+        self.residue(sub_mod(a.0.to_u128().unwrap(), b.0.to_u128().unwrap(), self.wrap.to_u128().unwrap()))
+    }
+
+    pub fn mul(&self, a: WrapVal<T>, b: WrapVal<T>) -> WrapVal<T> {
+        let (a, b) = (a.0.to_u128().unwrap(), b.0.to_u128().unwrap());
+
+        match a.checked_mul(b) {
+            Some(product) => WrapVal(self.reduce(product)),
+            None => self.residue(mul_mod(a, b, self.wrap.to_u128().unwrap())),
+        }
+    }
+
+    pub fn neg(&self, a: WrapVal<T>) -> WrapVal<T> {
+        self.residue(sub_mod(0, a.0.to_u128().unwrap(), self.wrap.to_u128().unwrap()))
+    }
+
+    pub fn to_wrap_num(&self, a: WrapVal<T>) -> WrapNum<T> {
+        WrapNum::new(a.0, self.wrap)
+    }
+
+    // Reduces the value of `num` into this modulus.
+    pub fn from_wrap_num(&self, num: WrapNum<T>) -> WrapVal<T> {
+        WrapVal(self.reduce(num.get_value().to_u128().unwrap()))
+    }
+
+    // WrapVal of a residue that's already below the wrap.
+    fn residue(&self, x: u128) -> WrapVal<T> {
+        WrapVal(NumCast::from(x).unwrap())
+    }
+}
+
+impl<T: UnsignedUnified> From<WrapNum<T>> for (Modulus<T>, WrapVal<T>) {
+    fn from(num: WrapNum<T>) -> Self {
+        (Modulus::new(num.get_wrap()), WrapVal(num.get_value()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::TestRng;
+
+    #[test]
+    fn wrap_val_size() {
+        assert_eq!(std::mem::size_of::<WrapVal<u64>>(), 8);
+        assert_eq!(std::mem::size_of::<[WrapVal<u64>; 16]>(), 128);
+    }
+
+    #[test]
+    fn reduce() {
+        let m = Modulus::new(6u32);
+
+        assert_eq!(m.reduce(15), 3);
+        assert_eq!(m.reduce(u128::MAX), (u128::MAX % 6) as u32);
+    }
+
+    fn check_reduce<T: UnsignedUnified>(wrap: T, rng: &mut TestRng) {
+        let m = Modulus::new(wrap);
+        let n = wrap.to_u128().unwrap();
+
+        for x in [0, 1, n - 1, n, n.wrapping_add(1), u128::MAX, u128::MAX - 1] {
+            assert_eq!(m.reduce(x).to_u128().unwrap(), x % n);
+        }
+        for _ in 0..200 {
+            let x = rng.next_u128();
+
+            assert_eq!(m.reduce(x).to_u128().unwrap(), x % n);
+        }
+    }
+
+    #[test]
+    fn reduce_against_rem() {
+        let mut rng = TestRng::new(0xba77e77);
+
+        for wrap in 1..64u64 {
+            check_reduce(wrap, &mut rng);
+        }
+        for k in 0..64u64 {
+            check_reduce(u64::MAX - k, &mut rng);
+        }
+        for _ in 0..200 {
+            check_reduce(rng.next_u64() | 1, &mut rng);
+            check_reduce((rng.below(u32::MAX as u64) + 1) as u32, &mut rng);
+            check_reduce(rng.next_u128() | 1, &mut rng);
+        }
+        check_reduce(u128::MAX, &mut rng);
+        check_reduce(1u128 << 127, &mut rng);
+    }
+
+    #[test]
+    fn mul_near_u64_max() {
+        let wrap = u64::MAX - 58;
+        let m = Modulus::new(wrap);
+        let mut rng = TestRng::new(7);
+
+        for _ in 0..1000 {
+            let (a, b) = (rng.below(wrap), rng.below(wrap));
+
+            assert_eq!(m.mul(m.val(a), m.val(b)).get() as u128, a as u128 * b as u128 % wrap as u128);
+        }
+    }
+
+    #[test]
+    fn mul_u128_falls_back() {
+        let m = Modulus::new(u128::MAX);
+
+        assert_eq!(m.mul(m.val(u128::MAX - 1), m.val(u128::MAX - 1)).get(), 1);
+    }
+
+    #[test]
+    fn wrapnum_factory() {
+        let m = Modulus::new(9u16);
+
+        assert_eq!(m.wrapnum(4), WrapNum::new(4, 9));
+        assert_eq!(m.wrapnum(40), WrapNum::new(4, 9));
+    }
+
+    #[test]
+    fn arithmetic_parity_with_wrap_num() {
+        let m = Modulus::new(13u32);
+
+        for a in 0..13u32 {
+            for b in 0..13u32 {
+                let num = WrapNum::new(a, 13);
+
+                assert_eq!(m.add(m.val(a), m.val(b)).get(), (num + b).get_value());
+                assert_eq!(m.mul(m.val(a), m.val(b)).get(), (num * b).get_value());
+                assert_eq!(m.sub(m.val(a), m.val(b)).get(), (num + -WrapNum::new(b, 13)).get_value());
+                assert_eq!(m.neg(m.val(a)).get(), (-num).get_value());
+            }
+        }
+    }
+
+    #[test]
+    fn add_sub_near_u128_max() {
+        let m = Modulus::new(u128::MAX);
+
+        assert_eq!(m.add(m.val(u128::MAX - 1), m.val(u128::MAX - 2)).get(), u128::MAX - 3);
+        assert_eq!(m.sub(m.val(1), m.val(u128::MAX - 1)).get(), 2);
+        assert_eq!(m.neg(m.val(1)).get(), u128::MAX - 1);
+        assert_eq!(m.neg(m.val(0)).get(), 0);
+    }
+
+    #[test]
+    fn sub_wraps() {
+        let m = Modulus::new(6u8);
+
+        assert_eq!(m.sub(m.val(1), m.val(4)).get(), 3);
+    }
+
+    #[test]
+    fn wrap_num_conversions() {
+        let num = WrapNum::new(4u64, 9u64);
+
+        let (m, val) = num.into();
+
+        assert_eq!(m.get_wrap(), 9);
+        assert_eq!(val.get(), 4);
+        assert_eq!(m.to_wrap_num(val), num);
+        assert_eq!(Modulus::new(3u64).from_wrap_num(num).get(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn new_zero_wrap() {
+        let _ = Modulus::new(0u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "value must be less than wrap")]
+    fn val_out_of_range() {
+        let m = Modulus::new(6u32);
+
+        m.val(6);
+    }
+}
@@ -0,0 +1,233 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
+use num::NumCast;
+use crate::arith::{add_mod, sub_mod};
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct MontgomeryWrapNum<T: UnsignedUnified> {
+    // value * R mod wrap, with R = 2^64.
+    value: T,
+    wrap: T,
+    // -wrap^-1 mod R.
+    wrap_neg_inv: u64,
+    // R^2 mod wrap.
+    r2: u64,
+}
+
+// Value kept in Montgomery form for a fixed odd wrap, so multiplication is a
+// Montgomery reduction instead of a hardware division. Addition and
+// subtraction work on the Montgomery form directly, and converting to and
+// from WrapNum happens only at the boundaries. The wrap must be odd and fit
+// in a u64.
+// When operating with multiple MontgomeryWrapNums, both must share the wrap.
+impl<T: UnsignedUnified> MontgomeryWrapNum<T> {
+    pub fn new(value: T, wrap: T) -> MontgomeryWrapNum<T> {
+        let n = wrap.to_u64().expect("wrap must fit in a u64");
+        assert!(n % 2 == 1, "wrap must be odd");
+        assert!(value < wrap, "value must be less than wrap");
+
+        let mut inv = n;
+        for _ in 0..5 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(inv)));
+        }
+        let r = (u64::MAX % n + 1) % n;
+        let r2 = ((r as u128 * r as u128) % n as u128) as u64;
+
+        let num = MontgomeryWrapNum {
+            value: T::zero(),
+            wrap,
+            wrap_neg_inv: inv.wrapping_neg(),
+            r2,
+        };
+
+        num.with_montgomery(num.redc(value.to_u64().unwrap() as u128 * r2 as u128))
+    }
+
+    pub fn get_value(self) -> T {
+        NumCast::from(self.redc(self.montgomery())).unwrap()
+    }
+
+    pub fn get_wrap(self) -> T {
+        self.wrap
+    }
+
+    pub fn to_wrap_num(self) -> WrapNum<T> {
+        WrapNum::new(self.get_value(), self.wrap)
+    }
+
+    fn n(self) -> u64 {
+        self.wrap.to_u64().unwrap()
+    }
+
+    fn montgomery(self) -> u128 {
+        self.value.to_u128().unwrap()
+    }
+
+    fn with_montgomery(self, value: u64) -> MontgomeryWrapNum<T> {
+        MontgomeryWrapNum {
+            value: NumCast::from(value).unwrap(),
+            ..self
+        }
+    }
+
+    // t * R^-1 mod wrap, for t < wrap * R.
+    fn redc(self, t: u128) -> u64 {
+        let n = self.n();
+        let m = (t as u64).wrapping_mul(self.wrap_neg_inv);
+        let (sum, carry) = t.overflowing_add(m as u128 * n as u128);
+        let u = (sum >> 64) | ((carry as u128) << 64);
+
+        if u >= n as u128 {
+            (u - n as u128) as u64
+        } else {
+            u as u64
+        }
+    }
+
+    fn check_wrap(self, rhs: MontgomeryWrapNum<T>) {
+        assert!(self.wrap == rhs.wrap, "wrap values differ");
+    }
+}
+
+impl<T: UnsignedUnified> From<WrapNum<T>> for MontgomeryWrapNum<T> {
+    fn from(num: WrapNum<T>) -> Self {
+        MontgomeryWrapNum::new(num.get_value(), num.get_wrap())
+    }
+}
+
+impl<T: UnsignedUnified> From<MontgomeryWrapNum<T>> for WrapNum<T> {
+    fn from(num: MontgomeryWrapNum<T>) -> Self {
+        num.to_wrap_num()
+    }
+}
+
+impl<T: UnsignedUnified> Add for MontgomeryWrapNum<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check_wrap(rhs);
+
+        self.with_montgomery(add_mod(self.montgomery(), rhs.montgomery(), self.n() as u128) as u64)
+    }
+}
+
+impl<T: UnsignedUnified> AddAssign for MontgomeryWrapNum<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+// Modular, wrapping below 0.
+impl<T: UnsignedUnified> Sub for MontgomeryWrapNum<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.check_wrap(rhs);
+
+        self.with_montgomery(sub_mod(self.montgomery(), rhs.montgomery(), self.n() as u128) as u64)
+    }
+}
+
+impl<T: UnsignedUnified> SubAssign for MontgomeryWrapNum<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: UnsignedUnified> Mul for MontgomeryWrapNum<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.check_wrap(rhs);
+
+        self.with_montgomery(self.redc(self.montgomery() * rhs.montgomery()))
+    }
+}
+
+impl<T: UnsignedUnified> MulAssign for MontgomeryWrapNum<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::TestRng;
+
+    #[test]
+    fn round_trip() {
+        for value in 0..15u32 {
+            assert_eq!(MontgomeryWrapNum::new(value, 15u32).get_value(), value);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be odd")]
+    fn even_wrap() {
+        let _ = MontgomeryWrapNum::new(1u32, 16u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "value must be less than wrap")]
+    fn value_out_of_range() {
+        let _ = MontgomeryWrapNum::new(13u32, 13u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must fit in a u64")]
+    fn wrap_too_large() {
+        let _ = MontgomeryWrapNum::new(1u128, u128::MAX);
+    }
+
+    #[test]
+    fn wrap_one() {
+        let num = MontgomeryWrapNum::new(0u8, 1u8);
+
+        assert_eq!((num * num + num).get_value(), 0);
+    }
+
+    #[test]
+    fn mul_near_u64_max() {
+        let wrap = u64::MAX;
+        let a = u64::MAX - 2;
+        let b = u64::MAX - 12345;
+
+        let num = MontgomeryWrapNum::new(a, wrap) * MontgomeryWrapNum::new(b, wrap);
+
+        assert_eq!(num.get_value() as u128, a as u128 * b as u128 % wrap as u128);
+    }
+
+    #[test]
+    fn parity_with_wrap_num() {
+        let mut rng = TestRng::new(0x5eed);
+
+        for _ in 0..50 {
+            let wrap = rng.next_u64() | 1;
+            let mut num = WrapNum::new(rng.below(wrap), wrap);
+            let mut mont = MontgomeryWrapNum::from(num);
+
+            for _ in 0..200 {
+                let rhs = WrapNum::new(rng.below(wrap), wrap);
+                let mont_rhs = MontgomeryWrapNum::from(rhs);
+
+                match rng.below(3) {
+                    0 => {
+                        num += rhs;
+                        mont += mont_rhs;
+                    }
+                    1 => {
+                        num = num.wrapping_sub(rhs);
+                        mont -= mont_rhs;
+                    }
+                    _ => {
+                        num *= rhs;
+                        mont *= mont_rhs;
+                    }
+                }
+
+                assert_eq!(WrapNum::from(mont), num);
+            }
+        }
+    }
+}
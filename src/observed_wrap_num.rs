@@ -0,0 +1,176 @@
+use std::ops::{AddAssign, SubAssign, Deref};
+use num::ToPrimitive;
+use crate::wrap_num::{WrapNum, UnsignedUnified, widen_rhs};
+
+// What happened when an operation wrapped: the value before the operation,
+// the delta that was applied and the number of laps the operation completed.
+// The delta and laps are u128, like the rhs of WrapNum's operators, so a
+// delta wider than T still reports every lap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrapEvent<T> {
+    pub old: T,
+    pub delta: u128,
+    pub laps: u128,
+}
+
+// WrapNum that calls `observer` whenever an operation wraps. The observer is
+// called once per completed lap, so a delta spanning three laps calls it three
+// times, each with `laps == 3`; that's linear in the laps, so a huge delta on
+// a small wrap makes a correspondingly huge number of calls. Operations that
+// don't wrap never call it. `sub` wraps downward past 0 like `wrapping_sub`
+// and counts the laps it goes back.
+// Read access to the inner WrapNum goes through Deref; the plain WrapNum
+// stays free of any callback overhead.
+pub struct ObservedWrapNum<T: UnsignedUnified, F: FnMut(WrapEvent<T>)> {
+    num: WrapNum<T>,
+    observer: F,
+}
+
+impl<T: UnsignedUnified, F: FnMut(WrapEvent<T>)> ObservedWrapNum<T, F> {
+    pub fn new(value: T, wrap: T, observer: F) -> ObservedWrapNum<T, F> {
+        ObservedWrapNum {
+            num: WrapNum::new(value, wrap),
+            observer,
+        }
+    }
+
+    pub fn add<U: ToPrimitive>(&mut self, delta: U) {
+        let delta = widen_rhs(delta);
+        let (value, wrap) = self.value_and_wrap();
+        let laps = delta / wrap + u128::from(delta % wrap >= wrap - value);
+
+        self.apply(delta, laps, self.num + delta);
+    }
+
+    pub fn sub<U: ToPrimitive>(&mut self, delta: U) {
+        let delta = widen_rhs(delta);
+        let (value, wrap) = self.value_and_wrap();
+        let laps = delta / wrap + u128::from(delta % wrap > value);
+
+        self.apply(delta, laps, self.num.wrapping_sub(delta));
+    }
+
+    pub fn increment(&mut self) {
+        self.add(1u8);
+    }
+
+    pub fn into_inner(self) -> WrapNum<T> {
+        self.num
+    }
+
+    fn value_and_wrap(&self) -> (u128, u128) {
+        (self.num.get_value().to_u128().unwrap(), self.num.get_wrap().to_u128().unwrap())
+    }
+
+    // Stores the result, then reports the laps one call at a time.
+    fn apply(&mut self, delta: u128, laps: u128, num: WrapNum<T>) {
+        let old = self.num.get_value();
+
+        self.num = num;
+
+        for _ in 0..laps {
+            (self.observer)(WrapEvent {
+                old,
+                delta,
+                laps,
+            });
+        }
+    }
+}
+
+impl<T: UnsignedUnified, F: FnMut(WrapEvent<T>)> Deref for ObservedWrapNum<T, F> {
+    type Target = WrapNum<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.num
+    }
+}
+
+impl<T: UnsignedUnified, F: FnMut(WrapEvent<T>), U: ToPrimitive> AddAssign<U> for ObservedWrapNum<T, F> {
+    fn add_assign(&mut self, rhs: U) {
+        self.add(rhs);
+    }
+}
+
+impl<T: UnsignedUnified, F: FnMut(WrapEvent<T>), U: ToPrimitive> SubAssign<U> for ObservedWrapNum<T, F> {
+    fn sub_assign(&mut self, rhs: U) {
+        self.sub(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_event_without_wrap() {
+        let mut events = Vec::new();
+        let mut num = ObservedWrapNum::new(0u32, 10u32, |event| events.push(event));
+
+        num += 4;
+        num.increment();
+        num.add(4);
+
+        assert_eq!(num.get_value(), 9);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn event_once_per_lap() {
+        let mut events = Vec::new();
+        let mut num = ObservedWrapNum::new(9u32, 10u32, |event| events.push(event));
+
+        num.increment();
+
+        assert_eq!(num.get_value(), 0);
+        assert_eq!(events, vec![WrapEvent { old: 9, delta: 1, laps: 1 }]);
+    }
+
+    #[test]
+    fn multi_lap_add() {
+        let mut events = Vec::new();
+        let mut num = ObservedWrapNum::new(5u32, 10u32, |event| events.push(event));
+
+        num += 27;
+
+        assert_eq!(num.into_inner(), WrapNum::new(2, 10));
+        assert_eq!(events, vec![WrapEvent { old: 5, delta: 27, laps: 3 }; 3]);
+    }
+
+    #[test]
+    fn add_u128_delta_wider_than_t() {
+        let mut laps = 0;
+        let mut num = ObservedWrapNum::new(200u8, 250u8, |_| laps += 1);
+
+        num += 1000u128;
+
+        assert_eq!(num.get_value(), 200);
+        assert_eq!(laps, 4);
+    }
+
+    #[test]
+    fn multi_lap_sub() {
+        let mut events = Vec::new();
+        let mut num = ObservedWrapNum::new(5u32, 10u32, |event| events.push(event));
+
+        num -= 5;
+        num -= 27;
+
+        assert_eq!(num.into_inner(), WrapNum::new(3, 10));
+        assert_eq!(events, vec![WrapEvent { old: 0, delta: 27, laps: 3 }; 3]);
+    }
+
+    #[test]
+    fn counts_laps_across_operations() {
+        let mut laps = 0;
+        let mut num = ObservedWrapNum::new(0u8, 3u8, |_| laps += 1);
+
+        for _ in 0..10 {
+            num.increment();
+        }
+        num += 6;
+        num.sub(2);
+
+        assert_eq!(laps, 6);
+    }
+}
@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+use std::ops::{AddAssign, SubAssign};
+use num::{NumCast, ToPrimitive};
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Odometer<T: UnsignedUnified, const N: usize> {
+    digits: [WrapNum<T>; N],
+}
+
+// Chain of WrapNums with carry propagation, most significant digit first,
+// e.g. `[hours (24), minutes (60), seconds (60)]`. Every digit keeps its own
+// wrap, so mixed radices work. Amounts are counted in units of the last digit.
+impl<T: UnsignedUnified, const N: usize> Odometer<T, N> {
+    pub fn new(digits: [WrapNum<T>; N]) -> Odometer<T, N> {
+        Odometer {
+            digits,
+        }
+    }
+
+    pub fn digits(&self) -> &[WrapNum<T>; N] {
+        &self.digits
+    }
+
+    // Adds `delta` and returns how many times the top digit overflowed.
+    pub fn add(&mut self, delta: u128) -> u128 {
+        let mut carry = delta;
+
+        for digit in self.digits.iter_mut().rev() {
+            if carry == 0 {
+                break;
+            }
+
+            let wrap = digit.get_wrap().to_u128().unwrap();
+            let (value, digit_carry) = digit.add_with_carry(NumCast::from(carry % wrap).unwrap());
+
+            *digit = value;
+            carry = carry / wrap + digit_carry.to_u128().unwrap();
+        }
+
+        carry
+    }
+
+    // Subtracts `delta` and returns how many times the top digit had to
+    // borrow, i.e. underflowed below all zeros.
+    pub fn sub(&mut self, delta: u128) -> u128 {
+        let mut borrow = delta;
+
+        for digit in self.digits.iter_mut().rev() {
+            if borrow == 0 {
+                break;
+            }
+
+            let wrap = digit.get_wrap().to_u128().unwrap();
+            let value = digit.get_value().to_u128().unwrap();
+            let rhs = borrow % wrap;
+
+            borrow /= wrap;
+            *digit = if rhs > value {
+                borrow += 1;
+                WrapNum::new(NumCast::from(wrap - (rhs - value)).unwrap(), digit.get_wrap())
+            } else {
+                *digit - rhs
+            };
+        }
+
+        borrow
+    }
+
+    // Flat value in units of the last digit.
+    pub fn total(&self) -> u128 {
+        self.digits.iter().fold(0u128, |total, digit| {
+            total.checked_mul(digit.get_wrap().to_u128().unwrap())
+                .and_then(|total| total.checked_add(digit.get_value().to_u128().unwrap()))
+                .expect("odometer total exceeds u128")
+        })
+    }
+
+    // Distributes a flat `total` over the digits, keeping their wraps.
+    // Returns how many times the top digit overflowed.
+    pub fn set_total(&mut self, total: u128) -> u128 {
+        let mut rest = total;
+
+        for digit in self.digits.iter_mut().rev() {
+            let wrap = digit.get_wrap().to_u128().unwrap();
+
+            *digit = WrapNum::new(NumCast::from(rest % wrap).unwrap(), digit.get_wrap());
+            rest /= wrap;
+        }
+
+        rest
+    }
+}
+
+// Lexicographic by digit, most significant first.
+impl<T: UnsignedUnified, const N: usize> PartialOrd for Odometer<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.digits.iter().map(|digit| (digit.get_value(), digit.get_wrap()))
+            .partial_cmp(other.digits.iter().map(|digit| (digit.get_value(), digit.get_wrap())))
+    }
+}
+
+impl<T: UnsignedUnified, const N: usize, U: ToPrimitive> AddAssign<U> for Odometer<T, N> {
+    fn add_assign(&mut self, rhs: U) {
+        self.add(rhs.to_u128().unwrap());
+    }
+}
+
+impl<T: UnsignedUnified, const N: usize, U: ToPrimitive> SubAssign<U> for Odometer<T, N> {
+    fn sub_assign(&mut self, rhs: U) {
+        self.sub(rhs.to_u128().unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(hours: u32, minutes: u32, seconds: u32) -> Odometer<u32, 3> {
+        Odometer::new([WrapNum::new(hours, 24), WrapNum::new(minutes, 60), WrapNum::new(seconds, 60)])
+    }
+
+    fn values(odometer: &Odometer<u32, 3>) -> [u32; 3] {
+        odometer.digits().map(|digit| digit.get_value())
+    }
+
+    #[test]
+    fn add_assign_distributes() {
+        let mut odometer = clock(0, 0, 0);
+
+        odometer += 3725;
+
+        assert_eq!(values(&odometer), [1, 2, 5]);
+    }
+
+    #[test]
+    fn add_cascade() {
+        let mut odometer = clock(0, 59, 59);
+
+        let overflow = odometer.add(1);
+
+        assert_eq!(values(&odometer), [1, 0, 0]);
+        assert_eq!(overflow, 0);
+    }
+
+    #[test]
+    fn add_top_overflow() {
+        let mut odometer = clock(23, 59, 59);
+
+        assert_eq!(odometer.add(1), 1);
+        assert_eq!(values(&odometer), [0, 0, 0]);
+
+        assert_eq!(odometer.add(3 * 86_400 + 61), 3);
+        assert_eq!(values(&odometer), [0, 1, 1]);
+    }
+
+    #[test]
+    fn sub_borrows_across_digits() {
+        let mut odometer = clock(2, 0, 5);
+
+        let underflow = odometer.sub(3606);
+
+        assert_eq!(values(&odometer), [0, 59, 59]);
+        assert_eq!(underflow, 0);
+    }
+
+    #[test]
+    fn sub_top_underflow() {
+        let mut odometer = clock(0, 0, 0);
+
+        assert_eq!(odometer.sub(1), 1);
+        assert_eq!(values(&odometer), [23, 59, 59]);
+
+        odometer -= 86_399;
+        assert_eq!(values(&odometer), [0, 0, 0]);
+    }
+
+    #[test]
+    fn mixed_radices() {
+        let mut odometer = Odometer::new([WrapNum::new(0u8, 3), WrapNum::new(0, 7), WrapNum::new(0, 2)]);
+
+        odometer += 29;
+
+        assert_eq!(odometer.digits().map(|digit| digit.get_value()), [2, 0, 1]);
+        assert_eq!(odometer.total(), 29);
+    }
+
+    #[test]
+    fn total_round_trip() {
+        let mut odometer = clock(0, 0, 0);
+
+        assert_eq!(odometer.set_total(86_400 + 45_296), 1);
+        assert_eq!(values(&odometer), [12, 34, 56]);
+        assert_eq!(odometer.total(), 45_296);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(clock(1, 0, 0) > clock(0, 59, 59));
+        assert!(clock(0, 0, 1) < clock(0, 1, 0));
+        assert_eq!(clock(3, 2, 1).partial_cmp(&clock(3, 2, 1)), Some(Ordering::Equal));
+    }
+}
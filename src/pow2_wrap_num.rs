@@ -0,0 +1,321 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
+use std::fmt;
+use std::error::Error;
+use num::traits::WrappingSub;
+use num::{NumCast, PrimInt, ToPrimitive};
+use crate::wrap_num::{WrapNum, WrapInteger, DefaultWithWrap, UnsignedUnified, widen_rhs, forward_to_primitive};
+
+// WrapNum restricted to power-of-two wraps, e.g. ring buffer capacities.
+// Reducing is a mask with wrap - 1, so no operation divides. Unlike WrapNum's
+// `-`, subtraction is modular, since masking wraps below 0 for free.
+// When operating with multiple Pow2WrapNums, the wrap value of the former is taken.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Pow2WrapNum<T: UnsignedUnified + PrimInt + WrappingSub> {
+    value: T,
+    mask: T,
+}
+
+// Error returned when a wrap isn't a power of two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotPowerOfTwoError(());
+
+impl fmt::Display for NotPowerOfTwoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("wrap must be a power of two")
+    }
+}
+
+impl Error for NotPowerOfTwoError {}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub> Pow2WrapNum<T> {
+    pub fn new(value: T, wrap: T) -> Pow2WrapNum<T> {
+        Pow2WrapNum::try_new(value, wrap).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // Panics unless value < wrap, like `WrapNum::new`.
+    pub fn try_new(value: T, wrap: T) -> Result<Pow2WrapNum<T>, NotPowerOfTwoError> {
+        if wrap.count_ones() != 1 {
+            return Err(NotPowerOfTwoError(()));
+        }
+        assert!(value < wrap, "value must be less than wrap");
+
+        Ok(Pow2WrapNum {
+            value,
+            mask: wrap - T::one(),
+        })
+    }
+
+    // Wrap of 2^log2, which must be less than the bit width of T.
+    pub fn from_log2(value: T, log2: u32) -> Pow2WrapNum<T> {
+        assert!(log2 < T::zero().count_zeros(), "2^log2 must fit in T");
+
+        Pow2WrapNum::new(value, T::one() << log2 as usize)
+    }
+
+    pub fn get_value(self) -> T {
+        self.value
+    }
+
+    pub fn get_wrap(self) -> T {
+        self.mask + T::one()
+    }
+
+    pub fn successor(self) -> Pow2WrapNum<T> {
+        self.with_value(self.value.wrapping_add(&T::one()))
+    }
+
+    pub fn predecessor(self) -> Pow2WrapNum<T> {
+        self.with_value(self.value.wrapping_sub(&T::one()))
+    }
+
+    pub fn to_wrap_num(self) -> WrapNum<T> {
+        WrapNum::new(self.value, self.get_wrap())
+    }
+
+    // Only the low bits of a rhs affect the result, so it can be wider than T.
+    fn rhs_low_bits<U: ToPrimitive>(self, rhs: U) -> T {
+        NumCast::from(widen_rhs(rhs) & self.mask.to_u128().unwrap()).unwrap()
+    }
+
+    // Masks `value`, which may be anything representable in T.
+    fn with_value(self, value: T) -> Pow2WrapNum<T> {
+        Pow2WrapNum {
+            value: value & self.mask,
+            mask: self.mask,
+        }
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub> From<Pow2WrapNum<T>> for WrapNum<T> {
+    fn from(num: Pow2WrapNum<T>) -> Self {
+        num.to_wrap_num()
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub> TryFrom<WrapNum<T>> for Pow2WrapNum<T> {
+    type Error = NotPowerOfTwoError;
+
+    fn try_from(num: WrapNum<T>) -> Result<Self, Self::Error> {
+        Pow2WrapNum::try_new(num.get_value(), num.get_wrap())
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub> WrapInteger<T> for Pow2WrapNum<T> {
+    fn get_value(self) -> T {
+        self.value
+    }
+
+    fn get_wrap(self) -> T {
+        Pow2WrapNum::get_wrap(self)
+    }
+
+    fn successor(self) -> Self {
+        Pow2WrapNum::successor(self)
+    }
+
+    fn wrapping_sub(self, rhs: T) -> Self {
+        self - rhs
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub> DefaultWithWrap<T> for Pow2WrapNum<T> {
+    fn default_with_wrap(wrap: T) -> Self {
+        Pow2WrapNum::new(T::zero(), wrap)
+    }
+}
+
+// Converts the value, so a Pow2WrapNum can be the right-hand side of any
+// wrapping operator.
+impl<T: UnsignedUnified + PrimInt + WrappingSub> ToPrimitive for Pow2WrapNum<T> {
+    forward_to_primitive!(
+        to_isize -> isize, to_i8 -> i8, to_i16 -> i16, to_i32 -> i32, to_i64 -> i64, to_i128 -> i128,
+        to_usize -> usize, to_u8 -> u8, to_u16 -> u16, to_u32 -> u32, to_u64 -> u64, to_u128 -> u128,
+        to_f32 -> f32, to_f64 -> f64
+    );
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub, U: ToPrimitive> Add<U> for Pow2WrapNum<T> {
+    type Output = Self;
+
+    fn add(self, rhs: U) -> Self::Output {
+        self.with_value(self.value.wrapping_add(&self.rhs_low_bits(rhs)))
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub, U: ToPrimitive> AddAssign<U> for Pow2WrapNum<T> {
+    fn add_assign(&mut self, rhs: U) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub, U: ToPrimitive> Sub<U> for Pow2WrapNum<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: U) -> Self::Output {
+        self.with_value(self.value.wrapping_sub(&self.rhs_low_bits(rhs)))
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub, U: ToPrimitive> SubAssign<U> for Pow2WrapNum<T> {
+    fn sub_assign(&mut self, rhs: U) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub, U: ToPrimitive> Mul<U> for Pow2WrapNum<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        self.with_value(self.value.wrapping_mul(&self.rhs_low_bits(rhs)))
+    }
+}
+
+impl<T: UnsignedUnified + PrimInt + WrappingSub, U: ToPrimitive> MulAssign<U> for Pow2WrapNum<T> {
+    fn mul_assign(&mut self, rhs: U) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng::TestRng;
+
+    #[test]
+    fn add_mask_wrap() {
+        let num = Pow2WrapNum::new(6u32, 8u32);
+
+        assert_eq!((num + 5u32).get_value(), 3);
+        assert_eq!((num - 7u32).get_value(), 7);
+        assert_eq!((num * 3u32).get_value(), 2);
+    }
+
+    #[test]
+    fn u128_rhs_above_type_max() {
+        let num = Pow2WrapNum::new(6u32, 8u32);
+        let big = u32::MAX as u128 * 3 + 5;
+
+        assert_eq!((num + big).get_value(), ((6 + big) % 8) as u32);
+        assert_eq!((num - big).get_value(), ((6 + 8 - big % 8) % 8) as u32);
+        assert_eq!((num * big).get_value(), ((6 * big) % 8) as u32);
+    }
+
+    #[test]
+    fn u128_value_as_rhs() {
+        let pow2 = Pow2WrapNum::new(1u128 << 100, 1 << 120);
+
+        assert_eq!(pow2.to_u128(), Some(1 << 100));
+        assert_eq!(pow2.to_u64(), None);
+        assert_eq!((WrapNum::new(0u128, 7) + pow2).get_value(), (1u128 << 100) % 7);
+    }
+
+    #[test]
+    fn not_power_of_two() {
+        assert_eq!(Pow2WrapNum::try_new(0u32, 12u32), Err(NotPowerOfTwoError(())));
+        assert_eq!(Pow2WrapNum::try_new(0u32, 0u32), Err(NotPowerOfTwoError(())));
+        assert_eq!(Pow2WrapNum::try_from(WrapNum::new(1u8, 6u8)), Err(NotPowerOfTwoError(())));
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be a power of two")]
+    fn new_not_power_of_two() {
+        let _ = Pow2WrapNum::new(0u16, 6u16);
+    }
+
+    #[test]
+    fn wrap_one() {
+        let mut num = Pow2WrapNum::from_log2(0u8, 0);
+
+        num += 200u8;
+        num = num.successor() * 7u8 - 3u8;
+
+        assert_eq!(num.get_value(), 0);
+        assert_eq!(num.get_wrap(), 1);
+    }
+
+    #[test]
+    fn wrap_half_type_width() {
+        let num = Pow2WrapNum::from_log2(127u8, 7);
+
+        assert_eq!(num.get_wrap(), 128);
+        assert_eq!(num.successor().get_value(), 0);
+        assert_eq!((num + 255u8).get_value(), 126);
+        assert_eq!((num * 255u8).get_value(), 1);
+        assert_eq!(num.predecessor().get_value(), 126);
+        assert_eq!(Pow2WrapNum::from_log2(0u8, 0).predecessor().get_value(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "value must be less than wrap")]
+    fn new_value_out_of_range() {
+        let _ = Pow2WrapNum::new(8u8, 8u8);
+    }
+
+    #[test]
+    #[should_panic(expected = "2^log2 must fit in T")]
+    fn log2_too_large() {
+        let _ = Pow2WrapNum::from_log2(0u8, 8);
+    }
+
+    #[test]
+    fn parity_with_wrap_num() {
+        let mut rng = TestRng::new(0xf00d);
+
+        for log2 in 0..64 {
+            let wrap = 1u64 << log2;
+            let mut num = WrapNum::new(rng.below(wrap), wrap);
+            let mut pow2 = Pow2WrapNum::try_from(num).unwrap();
+
+            for _ in 0..200 {
+                let rhs = rng.next_u64();
+
+                match rng.below(4) {
+                    0 => {
+                        num += rhs;
+                        pow2 += rhs;
+                    }
+                    1 => {
+                        num = num.wrapping_sub(rhs);
+                        pow2 -= rhs;
+                    }
+                    2 => {
+                        num *= rhs;
+                        pow2 *= rhs;
+                    }
+                    _ => {
+                        num = num.successor();
+                        pow2 = pow2.successor();
+                    }
+                }
+
+                assert_eq!(WrapNum::from(pow2), num);
+            }
+        }
+    }
+
+    fn cycle_len<N: WrapInteger<u32>>(start: N) -> u32 {
+        let mut num = start.successor();
+        let mut len = 1;
+
+        while num.get_value() != start.get_value() {
+            num = num.successor();
+            len += 1;
+        }
+
+        len
+    }
+
+    #[test]
+    fn shared_trait() {
+        assert_eq!(cycle_len(Pow2WrapNum::new(3u32, 16u32)), 16);
+        assert_eq!(cycle_len(WrapNum::new(3u32, 10u32)), 10);
+        assert_eq!(cycle_len(crate::FixedWrap::<u32, 5>::new(3)), 5);
+    }
+
+    #[test]
+    fn default_with_wrap() {
+        let num: Pow2WrapNum<u16> = DefaultWithWrap::default_with_wrap(64);
+
+        assert_eq!(num, Pow2WrapNum::from_log2(0, 6));
+    }
+}
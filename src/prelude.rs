@@ -0,0 +1,18 @@
+// The types and traits most code needs: `use wrap_num::prelude::*;`.
+pub use crate::wrap_num::{WrapNum, WrapInteger, UnsignedUnified};
+pub use crate::fixed_wrap::FixedWrap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn double<T: UnsignedUnified, N: WrapInteger<T>>(num: N) -> N {
+        num + num.get_value()
+    }
+
+    #[test]
+    fn prelude_is_enough_for_generic_code() {
+        assert_eq!(double(WrapNum::new(4u32, 6u32)).get_value(), 2);
+        assert_eq!(double(FixedWrap::<u32, 6>::new(4)).get_value(), 2);
+    }
+}
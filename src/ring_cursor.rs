@@ -0,0 +1,185 @@
+use std::iter;
+use crate::wrap_num::WrapNum;
+
+// Index into a circular buffer of fixed capacity. Head and tail cursors
+// alone can't tell a full buffer from an empty one, so anything that
+// depends on the fill level takes the number of used slots explicitly.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct RingCursor {
+    num: WrapNum<usize>,
+}
+
+impl RingCursor {
+    pub fn new(position: usize, capacity: usize) -> RingCursor {
+        RingCursor {
+            num: WrapNum::new(position, capacity),
+        }
+    }
+
+    // Cursor at index 0 of `buf`, which must not be empty.
+    pub fn for_slice<E>(buf: &[E]) -> RingCursor {
+        RingCursor::new(0, buf.len())
+    }
+
+    pub fn position(self) -> usize {
+        self.num.get_value()
+    }
+
+    pub fn capacity(self) -> usize {
+        self.num.get_wrap()
+    }
+
+    pub fn advance(&mut self, n: usize) {
+        self.num = self.num.advance(n);
+    }
+
+    // Steps forward from here to `other`, e.g. the used slots from head to
+    // tail. A full buffer reports 0, the same as an empty one.
+    pub fn distance_to(self, other: RingCursor) -> usize {
+        WrapNum::new(other.position() % self.capacity(), self.capacity())
+            .wrapping_sub(self.position())
+            .get_value()
+    }
+
+    // Free slots when `len_used` of them are taken.
+    pub fn remaining(self, len_used: usize) -> usize {
+        assert!(len_used <= self.capacity(), "more slots used than the capacity");
+
+        self.capacity() - len_used
+    }
+
+    // The next `count` indices from here, wrapping as often as needed.
+    pub fn indices(self, count: usize) -> impl Iterator<Item = usize> {
+        iter::successors(Some(self.num), |num| Some(num.successor()))
+            .take(count)
+            .map(WrapNum::get_value)
+    }
+}
+
+impl From<WrapNum<usize>> for RingCursor {
+    fn from(num: WrapNum<usize>) -> Self {
+        RingCursor { num }
+    }
+}
+
+impl From<RingCursor> for WrapNum<usize> {
+    fn from(cursor: RingCursor) -> Self {
+        cursor.num
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use super::*;
+    use crate::test_rng::TestRng;
+
+    // Fixed-capacity queue built on a head cursor and a fill count.
+    struct RingBuffer {
+        buf: Vec<u64>,
+        head: RingCursor,
+        len: usize,
+    }
+
+    impl RingBuffer {
+        fn new(capacity: usize) -> RingBuffer {
+            let buf = vec![0; capacity];
+            let head = RingCursor::for_slice(&buf);
+
+            RingBuffer { buf, head, len: 0 }
+        }
+
+        fn tail(&self) -> RingCursor {
+            let mut tail = self.head;
+            tail.advance(self.len);
+            tail
+        }
+
+        fn push_back(&mut self, value: u64) -> bool {
+            if self.head.remaining(self.len) == 0 {
+                return false;
+            }
+
+            let tail = self.tail().position();
+            self.buf[tail] = value;
+            self.len += 1;
+            true
+        }
+
+        fn pop_front(&mut self) -> Option<u64> {
+            if self.len == 0 {
+                return None;
+            }
+
+            let value = self.buf[self.head.position()];
+            self.head.advance(1);
+            self.len -= 1;
+            Some(value)
+        }
+
+        fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+            self.head.indices(self.len).map(|index| self.buf[index])
+        }
+    }
+
+    #[test]
+    fn ring_buffer_against_vec_deque() {
+        let mut rng = TestRng::new(0xc0ffee);
+
+        for capacity in 1..12 {
+            let mut ring = RingBuffer::new(capacity);
+            let mut reference = VecDeque::new();
+
+            for _ in 0..500 {
+                if rng.below(2) == 0 {
+                    let value = rng.next_u64();
+                    let pushed = ring.push_back(value);
+
+                    assert_eq!(pushed, reference.len() < capacity);
+                    if pushed {
+                        reference.push_back(value);
+                    }
+                } else {
+                    assert_eq!(ring.pop_front(), reference.pop_front());
+                }
+
+                assert_eq!(ring.head.distance_to(ring.tail()), reference.len() % capacity);
+                assert_eq!(ring.head.remaining(ring.len), capacity - reference.len());
+                assert!(ring.iter().eq(reference.iter().copied()));
+            }
+        }
+    }
+
+    #[test]
+    fn for_slice_capacity() {
+        let buf = [0u8; 16];
+
+        let cursor = RingCursor::for_slice(&buf);
+
+        assert_eq!(cursor.capacity(), 16);
+        assert_eq!(cursor.position(), 0);
+    }
+
+    #[test]
+    fn distance_to_across_seam() {
+        let head = RingCursor::new(6, 8);
+
+        assert_eq!(head.distance_to(RingCursor::new(1, 8)), 3);
+        assert_eq!(RingCursor::new(1, 8).distance_to(head), 5);
+        assert_eq!(head.distance_to(head), 0);
+    }
+
+    #[test]
+    fn indices_wrap_more_than_once() {
+        let cursor = RingCursor::new(2, 3);
+
+        assert_eq!(cursor.indices(7).collect::<Vec<_>>(), vec![2, 0, 1, 2, 0, 1, 2]);
+        assert_eq!(cursor.indices(0).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "more slots used than the capacity")]
+    fn remaining_over_capacity() {
+        RingCursor::new(0, 4).remaining(5);
+    }
+}
@@ -0,0 +1,96 @@
+use std::cmp::Ordering;
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+// Sequence number whose `<` is the wraparound-safe serial comparison of
+// RFC 1982 instead of WrapNum's linear order: `a < b` iff
+// `0 < (b - a) mod wrap < wrap / 2`. At exactly half the ring, which only
+// an even wrap has, neither is less and `partial_cmp` is None. Sequence
+// numbers of different wraps don't compare at all.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SeqWrap<T: UnsignedUnified> {
+    num: WrapNum<T>,
+}
+
+impl<T: UnsignedUnified> SeqWrap<T> {
+    pub fn new(value: T, wrap: T) -> SeqWrap<T> {
+        SeqWrap {
+            num: WrapNum::new(value, wrap),
+        }
+    }
+
+    pub fn get_value(self) -> T {
+        self.num.get_value()
+    }
+
+    pub fn get_wrap(self) -> T {
+        self.num.get_wrap()
+    }
+
+    pub fn successor(self) -> SeqWrap<T> {
+        SeqWrap { num: self.num.successor() }
+    }
+
+    pub fn advance(self, n: T) -> SeqWrap<T> {
+        SeqWrap { num: self.num.advance(n) }
+    }
+
+    pub fn to_wrap_num(self) -> WrapNum<T> {
+        self.num
+    }
+}
+
+impl<T: UnsignedUnified> PartialOrd for SeqWrap<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.get_wrap() != other.get_wrap() {
+            return None;
+        }
+
+        self.num.serial_cmp(&other.num)
+    }
+}
+
+impl<T: UnsignedUnified> From<WrapNum<T>> for SeqWrap<T> {
+    fn from(num: WrapNum<T>) -> Self {
+        SeqWrap { num }
+    }
+}
+
+impl<T: UnsignedUnified> From<SeqWrap<T>> for WrapNum<T> {
+    fn from(seq: SeqWrap<T>) -> Self {
+        seq.num
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straddles_wrap() {
+        let seq = |v| SeqWrap::new(v, 256u16);
+
+        assert!(seq(250) < seq(3));
+        assert!(seq(3) > seq(250));
+        assert!(seq(255) < seq(255).successor());
+        assert!(seq(100) < seq(101));
+        assert!(seq(0) > seq(200));
+    }
+
+    #[test]
+    fn half_ring_undefined() {
+        let seq = |v| SeqWrap::new(v, 256u16);
+
+        assert_eq!(seq(10).partial_cmp(&seq(138)), None);
+        assert!(!seq(10).lt(&seq(138)) && !seq(10).gt(&seq(138)));
+        assert_eq!(seq(10).partial_cmp(&seq(137)), Some(Ordering::Less));
+        assert_eq!(SeqWrap::new(0u8, 7u8).partial_cmp(&SeqWrap::new(3, 7)), Some(Ordering::Less));
+        assert_eq!(SeqWrap::new(0u8, 7u8).partial_cmp(&SeqWrap::new(4, 7)), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn equal_and_different_wraps() {
+        assert_eq!(SeqWrap::new(5u8, 10u8).partial_cmp(&SeqWrap::new(5, 10)), Some(Ordering::Equal));
+        assert_eq!(SeqWrap::new(5u8, 10u8).partial_cmp(&SeqWrap::new(5, 12)), None);
+        assert_eq!(SeqWrap::from(WrapNum::new(7u8, 10u8)).advance(5).to_wrap_num(), WrapNum::new(2, 10));
+    }
+}
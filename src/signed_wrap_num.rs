@@ -0,0 +1,344 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg};
+use num::{Signed, PrimInt, NumCast, ToPrimitive};
+use crate::arith::{add_mod, sub_mod, mul_mod, rem_euclid_i128};
+use crate::wrap_num::{WrapNum, UnsignedUnified, widen_rhs, forward_to_primitive};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SignedWrapNum<T: Signed + PrimInt> {
+    value: T,
+    min: T,
+    max: T,
+}
+
+// Signed type that wraps within [min, max) with Euclidean remainder semantics,
+// so -190 in -180..180 becomes 170.
+// All arithmetic is done on residues modulo max - min in u128, so it never
+// overflows T, not even around T::MIN.
+// When operating with multiple SignedWrapNums, the range of the former is taken.
+impl<T: Signed + PrimInt> SignedWrapNum<T> {
+    pub fn new(value: T, min: T, max: T) -> SignedWrapNum<T> {
+        assert!(min < max, "min must be less than max");
+
+        let num = SignedWrapNum {
+            value: min,
+            min,
+            max,
+        };
+
+        num.with_residue(num.residue_of(value.to_i128().unwrap()))
+    }
+
+    pub fn get_value(self) -> T {
+        self.value
+    }
+
+    pub fn get_min(self) -> T {
+        self.min
+    }
+
+    pub fn get_max(self) -> T {
+        self.max
+    }
+
+    // Number of values in the range, max - min.
+    pub fn span(self) -> u128 {
+        self.max.to_i128().unwrap().wrapping_sub(self.min.to_i128().unwrap()) as u128
+    }
+
+    // Shortest distance to `other` going either way around the range.
+    pub fn distance(self, other: SignedWrapNum<T>) -> u128 {
+        let span = self.span();
+        let forward = sub_mod(self.residue_of(other.value.to_i128().unwrap()),
+                              self.residue_of(self.value.to_i128().unwrap()), span);
+
+        forward.min(span - forward)
+    }
+
+    // Maps the range onto 0..span, i.e. min becomes 0.
+    pub fn to_unsigned<U: UnsignedUnified>(self) -> WrapNum<U> {
+        let span = self.span();
+
+        WrapNum::new(NumCast::from(self.offset()).unwrap(), NumCast::from(span).unwrap())
+    }
+
+    // Inverse of `to_unsigned`: 0 becomes `min` and the range is min..min + wrap.
+    pub fn from_unsigned<U: UnsignedUnified>(num: WrapNum<U>, min: T) -> SignedWrapNum<T> {
+        let min_i = min.to_i128().unwrap();
+        let max = min_i.checked_add(num.get_wrap().to_i128().unwrap()).unwrap();
+        let value = min_i + num.get_value().to_i128().unwrap();
+
+        SignedWrapNum::new(NumCast::from(value).unwrap(), min, NumCast::from(max).unwrap())
+    }
+
+    // Residue of `x` modulo the span.
+    fn residue_of(self, x: i128) -> u128 {
+        rem_euclid_i128(x, self.span())
+    }
+
+    fn rhs_residue<U: ToPrimitive>(self, rhs: U) -> u128 {
+        match rhs.to_i128() {
+            Some(rhs) => self.residue_of(rhs),
+            None => widen_rhs(rhs) % self.span(),
+        }
+    }
+
+    // Distance from min going forward.
+    fn offset(self) -> u128 {
+        let span = self.span();
+
+        sub_mod(self.residue_of(self.value.to_i128().unwrap()),
+                self.residue_of(self.min.to_i128().unwrap()), span)
+    }
+
+    // The value in [min, max) congruent to `residue`.
+    fn with_residue(self, residue: u128) -> SignedWrapNum<T> {
+        let span = self.span();
+        let offset = sub_mod(residue, self.residue_of(self.min.to_i128().unwrap()), span);
+        let value = self.min.to_i128().unwrap().wrapping_add(offset as i128);
+
+        SignedWrapNum {
+            value: NumCast::from(value).unwrap(),
+            min: self.min,
+            max: self.max,
+        }
+    }
+
+    fn value_residue(self) -> u128 {
+        self.residue_of(self.value.to_i128().unwrap())
+    }
+}
+
+impl<T: Signed + PrimInt> ToPrimitive for SignedWrapNum<T> {
+    forward_to_primitive!(
+        to_isize -> isize, to_i8 -> i8, to_i16 -> i16, to_i32 -> i32, to_i64 -> i64, to_i128 -> i128,
+        to_usize -> usize, to_u8 -> u8, to_u16 -> u16, to_u32 -> u32, to_u64 -> u64, to_u128 -> u128,
+        to_f32 -> f32, to_f64 -> f64
+    );
+}
+
+impl<T: Signed + PrimInt, U: ToPrimitive> Add<U> for SignedWrapNum<T> {
+    type Output = Self;
+
+    fn add(self, rhs: U) -> Self::Output {
+        self.with_residue(add_mod(self.value_residue(), self.rhs_residue(rhs), self.span()))
+    }
+}
+
+impl<T: Signed + PrimInt, U: ToPrimitive> AddAssign<U> for SignedWrapNum<T> {
+    fn add_assign(&mut self, rhs: U) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Signed + PrimInt, U: ToPrimitive> Sub<U> for SignedWrapNum<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: U) -> Self::Output {
+        self.with_residue(sub_mod(self.value_residue(), self.rhs_residue(rhs), self.span()))
+    }
+}
+
+impl<T: Signed + PrimInt, U: ToPrimitive> SubAssign<U> for SignedWrapNum<T> {
+    fn sub_assign(&mut self, rhs: U) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Signed + PrimInt, U: ToPrimitive> Mul<U> for SignedWrapNum<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        self.with_residue(mul_mod(self.value_residue(), self.rhs_residue(rhs), self.span()))
+    }
+}
+
+impl<T: Signed + PrimInt, U: ToPrimitive> MulAssign<U> for SignedWrapNum<T> {
+    fn mul_assign(&mut self, rhs: U) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Signed + PrimInt> Neg for SignedWrapNum<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.with_residue(sub_mod(0, self.value_residue(), self.span()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_normalizes_below_min() {
+        let num = SignedWrapNum::new(-190i32, -180i32, 180i32);
+
+        assert_eq!(num.get_value(), 170);
+    }
+
+    #[test]
+    fn to_u128_above_u64_max() {
+        let big = i128::MAX - 5;
+        let num = SignedWrapNum::new(big, 0i128, i128::MAX);
+
+        assert_eq!(num.to_u128(), Some(big as u128));
+        assert_eq!(num.to_u64(), None);
+        assert_eq!(SignedWrapNum::new(-5i128, -10, 10).to_u128(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "min must be less than max")]
+    fn new_empty_range() {
+        let _ = SignedWrapNum::new(3i32, 5i32, 5i32);
+    }
+
+    #[test]
+    fn new_normalizes_above_max() {
+        let num = SignedWrapNum::new(540i32, -180i32, 180i32);
+
+        assert_eq!(num.get_value(), -180);
+    }
+
+    #[test]
+    fn add_crossing_max() {
+        let num1 = SignedWrapNum::new(170i32, -180i32, 180i32);
+
+        let num2 = num1 + 20;
+
+        assert_eq!(num2.get_value(), -170);
+        assert_eq!(num2.get_min(), -180);
+        assert_eq!(num2.get_max(), 180);
+    }
+
+    #[test]
+    fn add_negative_operand() {
+        let num1 = SignedWrapNum::new(10i32, -180i32, 180i32);
+
+        let num2 = num1 + -200i32;
+
+        assert_eq!(num2.get_value(), 170);
+    }
+
+    #[test]
+    fn sub_crossing_min() {
+        let mut num = SignedWrapNum::new(-170i32, -180i32, 180i32);
+
+        num -= 20;
+
+        assert_eq!(num.get_value(), 170);
+    }
+
+    #[test]
+    fn sub_negative_operand() {
+        let num1 = SignedWrapNum::new(170i32, -180i32, 180i32);
+
+        let num2 = num1 - -20i32;
+
+        assert_eq!(num2.get_value(), -170);
+    }
+
+    #[test]
+    fn mul_negative_operand() {
+        let num1 = SignedWrapNum::new(100i32, -180i32, 180i32);
+
+        let num2 = num1 * -2i32;
+
+        assert_eq!(num2.get_value(), 160);
+    }
+
+    #[test]
+    fn mul_assign_wrap() {
+        let mut num = SignedWrapNum::new(100i32, -180i32, 180i32);
+
+        num *= 2;
+
+        assert_eq!(num.get_value(), -160);
+    }
+
+    #[test]
+    fn neg() {
+        let num = SignedWrapNum::new(90i32, -180i32, 180i32);
+
+        assert_eq!((-num).get_value(), -90);
+        assert_eq!((-(-num)).get_value(), 90);
+    }
+
+    #[test]
+    fn neg_min_of_symmetric_range() {
+        let num = SignedWrapNum::new(-180i32, -180i32, 180i32);
+
+        assert_eq!((-num).get_value(), -180);
+    }
+
+    #[test]
+    fn neg_type_min() {
+        let num = SignedWrapNum::new(i8::MIN, i8::MIN, i8::MAX);
+
+        assert_eq!((-num).get_value(), -127);
+    }
+
+    #[test]
+    fn add_crossing_type_max() {
+        let num = SignedWrapNum::new(i64::MAX - 1, i64::MIN, i64::MAX);
+
+        assert_eq!((num + 1).get_value(), i64::MIN);
+        assert_eq!((num + i64::MAX).get_value(), -2);
+    }
+
+    #[test]
+    fn sub_type_min_full_i128_range() {
+        let num = SignedWrapNum::new(i128::MIN, i128::MIN, i128::MAX);
+
+        let num = num - 1;
+
+        assert_eq!(num.get_value(), i128::MAX - 1);
+    }
+
+    #[test]
+    fn mul_type_extremes() {
+        let num = SignedWrapNum::new(i128::MIN + 1, i128::MIN, i128::MAX);
+
+        let num = num * i128::MAX;
+
+        assert_eq!(num.get_value(), -(1 << 126));
+    }
+
+    #[test]
+    fn distance_across_both_boundaries() {
+        let num1 = SignedWrapNum::new(-170i32, -180i32, 180i32);
+        let num2 = SignedWrapNum::new(170i32, -180i32, 180i32);
+
+        assert_eq!(num1.distance(num2), 20);
+        assert_eq!(num2.distance(num1), 20);
+    }
+
+    #[test]
+    fn distance_type_min_and_max() {
+        let num1 = SignedWrapNum::new(i8::MIN, i8::MIN, i8::MAX);
+        let num2 = SignedWrapNum::new(i8::MAX - 1, i8::MIN, i8::MAX);
+
+        assert_eq!(num1.distance(num2), 1);
+    }
+
+    #[test]
+    fn to_unsigned_offsets_by_min() {
+        let num = SignedWrapNum::new(-170i32, -180i32, 180i32);
+
+        let unsigned: WrapNum<u32> = num.to_unsigned();
+
+        assert_eq!(unsigned.get_value(), 10);
+        assert_eq!(unsigned.get_wrap(), 360);
+    }
+
+    #[test]
+    fn from_unsigned_round_trip() {
+        let num = SignedWrapNum::new(i8::MIN, i8::MIN, i8::MAX);
+
+        let unsigned: WrapNum<u8> = num.to_unsigned();
+        let back = SignedWrapNum::from_unsigned(unsigned, i8::MIN);
+
+        assert_eq!(unsigned.get_value(), 0);
+        assert_eq!(back, num);
+    }
+}
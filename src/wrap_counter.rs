@@ -0,0 +1,82 @@
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+// Counter that starts at 0 and ticks up by one, rolling back to 0 at the
+// wrap. Unlike CountingWrapNum it keeps no lap count: `tick` reports each
+// wrap and callers count them if they care.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct WrapCounter<T: UnsignedUnified> {
+    num: WrapNum<T>,
+}
+
+impl<T: UnsignedUnified> WrapCounter<T> {
+    pub fn new(wrap: T) -> WrapCounter<T> {
+        WrapCounter {
+            num: WrapNum::default_with_wrap(wrap),
+        }
+    }
+
+    // Steps forward once, returning true if the count rolled over to 0.
+    pub fn tick(&mut self) -> bool {
+        self.num.successor_assign();
+
+        self.num.is_zero()
+    }
+
+    pub fn reset(&mut self) {
+        self.num = WrapNum::default_with_wrap(self.num.get_wrap());
+    }
+
+    pub fn count(&self) -> T {
+        self.num.get_value()
+    }
+
+    pub fn get_wrap(&self) -> T {
+        self.num.get_wrap()
+    }
+}
+
+impl<T: UnsignedUnified> From<WrapCounter<T>> for WrapNum<T> {
+    fn from(counter: WrapCounter<T>) -> Self {
+        counter.num
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_full_cycle() {
+        let mut counter = WrapCounter::new(4u8);
+        let mut wraps = Vec::new();
+
+        for _ in 0..9 {
+            wraps.push(counter.tick());
+        }
+
+        assert_eq!(wraps, [false, false, false, true, false, false, false, true, false]);
+        assert_eq!(counter.count(), 1);
+    }
+
+    #[test]
+    fn wrap_one_wraps_every_tick() {
+        let mut counter = WrapCounter::new(1u32);
+
+        assert!(counter.tick());
+        assert!(counter.tick());
+        assert_eq!(counter.count(), 0);
+    }
+
+    #[test]
+    fn reset() {
+        let mut counter = WrapCounter::new(10u16);
+        counter.tick();
+        counter.tick();
+
+        counter.reset();
+
+        assert_eq!(counter.count(), 0);
+        assert_eq!(counter.get_wrap(), 10);
+        assert_eq!(WrapNum::from(counter), WrapNum::new(0, 10));
+    }
+}
@@ -0,0 +1,290 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use num::{Float, NumCast, ToPrimitive};
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WrapFloat<F: Float> {
+    value: F,
+    wrap: F,
+}
+
+// Float that wraps back into [0, wrap), e.g. phases and angles.
+// The value is always stored normalized, so equality compares the canonical
+// representative.
+// When operating with multiple WrapFloats, the wrap value of the former is taken.
+impl<F: Float> WrapFloat<F> {
+    pub fn new(value: F, wrap: F) -> WrapFloat<F> {
+        assert!(wrap.is_finite() && wrap > F::zero(), "wrap must be finite and positive");
+        assert!(value.is_finite(), "value must be finite");
+
+        WrapFloat {
+            value: normalize(value, wrap),
+            wrap,
+        }
+    }
+
+    pub fn get_value(self) -> F {
+        self.value
+    }
+
+    pub fn get_wrap(self) -> F {
+        self.wrap
+    }
+
+    // Shortest signed distance from `self` to `other`, in [-wrap/2, wrap/2).
+    pub fn signed_distance(self, other: WrapFloat<F>) -> F {
+        let half = self.wrap / (F::one() + F::one());
+        let distance = normalize(other.value - self.value, self.wrap);
+
+        if distance >= half {
+            distance - self.wrap
+        } else {
+            distance
+        }
+    }
+
+    // Length of the shorter arc between `self` and `other`.
+    pub fn abs_diff(self, other: WrapFloat<F>) -> F {
+        self.signed_distance(other).abs()
+    }
+
+    // Interpolates along the shorter arc, `t` = 0 gives `self` and 1 gives `other`.
+    pub fn lerp(self, other: WrapFloat<F>, t: F) -> WrapFloat<F> {
+        self + self.signed_distance(other) * t
+    }
+
+    // Scales the value into an integer ring with the given wrap, rounding to
+    // the nearest integer.
+    pub fn to_wrap_num<T: UnsignedUnified>(self, wrap: T) -> WrapNum<T> {
+        let wrap_f: F = NumCast::from(wrap).unwrap();
+        let scaled = (self.value / self.wrap * wrap_f).round();
+        let scaled = if scaled >= wrap_f { F::zero() } else { scaled };
+
+        WrapNum::new(NumCast::from(scaled).unwrap(), wrap)
+    }
+
+    // Scales an integer ring position into [0, wrap).
+    pub fn from_wrap_num<T: UnsignedUnified>(num: WrapNum<T>, wrap: F) -> WrapFloat<F> {
+        let value: F = NumCast::from(num.get_value()).unwrap();
+        let num_wrap: F = NumCast::from(num.get_wrap()).unwrap();
+
+        WrapFloat::new(value / num_wrap * wrap, wrap)
+    }
+}
+
+// Euclidean remainder that never returns `wrap` itself, which plain
+// `value % wrap + wrap` can after rounding for tiny negative values.
+fn normalize<F: Float>(value: F, wrap: F) -> F {
+    let mut value = value % wrap;
+
+    if value < F::zero() {
+        value = value + wrap;
+    }
+
+    if value >= wrap || value == F::zero() {
+        F::zero()
+    } else {
+        value
+    }
+}
+
+// Converts a right-hand side into `F`, rejecting infinities and NaN the same
+// way `new` does, since they would turn the value into NaN.
+fn finite_rhs<F: Float, U: ToPrimitive>(rhs: U) -> F {
+    let rhs: F = NumCast::from(rhs).unwrap();
+    assert!(rhs.is_finite(), "right-hand side must be finite");
+
+    rhs
+}
+
+impl<F: Float> ToPrimitive for WrapFloat<F> {
+    fn to_i64(&self) -> Option<i64> {
+        self.value.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.value.to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.value.to_f64()
+    }
+}
+
+impl<F: Float, U: ToPrimitive> Add<U> for WrapFloat<F> {
+    type Output = Self;
+
+    fn add(self, rhs: U) -> Self::Output {
+        let rhs = normalize(finite_rhs(rhs), self.wrap);
+
+        Self {
+            value: normalize(self.value + rhs, self.wrap),
+            wrap: self.wrap
+        }
+    }
+}
+
+impl<F: Float, U: ToPrimitive> AddAssign<U> for WrapFloat<F> {
+    fn add_assign(&mut self, rhs: U) {
+        *self = *self + rhs;
+    }
+}
+
+impl<F: Float, U: ToPrimitive> Sub<U> for WrapFloat<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: U) -> Self::Output {
+        let rhs = normalize(finite_rhs(rhs), self.wrap);
+
+        Self {
+            value: normalize(self.value - rhs, self.wrap),
+            wrap: self.wrap
+        }
+    }
+}
+
+impl<F: Float, U: ToPrimitive> SubAssign<U> for WrapFloat<F> {
+    fn sub_assign(&mut self, rhs: U) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_just_below_wrap() {
+        let num = WrapFloat::new(360.0 - 1e-13, 360.0);
+
+        assert!(num.get_value() < 360.0);
+        assert_eq!(num.get_value(), 360.0 - 1e-13);
+    }
+
+    #[test]
+    fn new_rounding_to_wrap_normalizes_to_zero() {
+        let num = WrapFloat::new(-1e-20, 360.0);
+
+        assert_eq!(num.get_value(), 0.0);
+    }
+
+    #[test]
+    fn new_negative() {
+        let num = WrapFloat::new(-90.0, 360.0);
+
+        assert_eq!(num.get_value(), 270.0);
+        assert_eq!(num, WrapFloat::new(270.0, 360.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_nan_wrap() {
+        let _ = WrapFloat::new(1.0, f64::NAN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_infinite_wrap() {
+        let _ = WrapFloat::new(1.0, f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_negative_wrap() {
+        let _ = WrapFloat::new(1.0, -360.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_nan_value() {
+        let _ = WrapFloat::new(f32::NAN, 360.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "right-hand side must be finite")]
+    fn add_infinite_delta() {
+        let mut num = WrapFloat::new(1.0, 360.0);
+        num += f64::INFINITY;
+    }
+
+    #[test]
+    #[should_panic(expected = "right-hand side must be finite")]
+    fn sub_nan_delta() {
+        let _ = WrapFloat::new(1.0, 360.0) - f64::NAN;
+    }
+
+    #[test]
+    fn sub_negative_delta() {
+        let num = WrapFloat::new(10.0, 360.0);
+
+        assert_eq!((num - 20.0).get_value(), 350.0);
+        assert_eq!((num + -20.0).get_value(), 350.0);
+    }
+
+    #[test]
+    fn add_large_delta() {
+        let mut num = WrapFloat::new(10.0f32, 360.0f32);
+
+        num += 1090.0f32;
+
+        assert_eq!(num.get_value(), 20.0);
+    }
+
+    #[test]
+    fn accumulation_drift() {
+        let mut num = WrapFloat::new(0.0, 1.0);
+
+        for _ in 0..1_000_000 {
+            num += 1e-3;
+        }
+
+        assert!(num.abs_diff(WrapFloat::new(0.0, 1.0)) < 1e-9);
+    }
+
+    #[test]
+    fn distance_across_seam() {
+        let num1 = WrapFloat::new(350.0, 360.0);
+        let num2 = WrapFloat::new(10.0, 360.0);
+
+        assert_eq!(num1.signed_distance(num2), 20.0);
+        assert_eq!(num2.signed_distance(num1), -20.0);
+        assert_eq!(num2.abs_diff(num1), 20.0);
+    }
+
+    #[test]
+    fn lerp_across_seam() {
+        let num1 = WrapFloat::new(350.0, 360.0);
+        let num2 = WrapFloat::new(10.0, 360.0);
+
+        assert_eq!(num1.lerp(num2, 0.0), num1);
+        assert_eq!(num1.lerp(num2, 0.25).get_value(), 355.0);
+        assert_eq!(num1.lerp(num2, 0.5).get_value(), 0.0);
+        assert_eq!(num1.lerp(num2, 1.0), num2);
+    }
+
+    #[test]
+    fn to_wrap_num_scales() {
+        let num = WrapFloat::new(90.0, 360.0);
+
+        let num = num.to_wrap_num(4u32);
+
+        assert_eq!(num.get_value(), 1);
+        assert_eq!(num.get_wrap(), 4);
+    }
+
+    #[test]
+    fn to_wrap_num_rounds_up_to_zero() {
+        let num = WrapFloat::new(359.9, 360.0);
+
+        assert_eq!(num.to_wrap_num(360u32).get_value(), 0);
+    }
+
+    #[test]
+    fn from_wrap_num_scales() {
+        let num = WrapNum::new(3u32, 4u32);
+
+        let num = WrapFloat::from_wrap_num(num, 360.0);
+
+        assert_eq!(num.get_value(), 270.0);
+    }
+}
@@ -1,280 +1,3214 @@
-use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Rem, RemAssign};
-use std::hash::Hash;
-use num::traits::{WrappingAdd, WrappingMul};
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Rem, RemAssign, Neg, Not};
+use std::hash::{Hash, Hasher};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::error::Error;
+use num::traits::{WrappingAdd, WrappingSub, WrappingMul, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv};
+use num::traits::{ToBytes, FromBytes};
+use num::traits::{SaturatingAdd, SaturatingSub, SaturatingMul, Euclid, CheckedEuclid, Pow, Inv, MulAdd, MulAddAssign};
 use num::{Unsigned, NumCast, ToPrimitive};
+use crate::arith::{add_mod, sub_mod, mul_mod, mul_div, rem_euclid_i128};
 
-pub trait UnsignedUnified: Unsigned + NumCast + PartialOrd + Copy + WrappingAdd + WrappingMul {}
-impl<T> UnsignedUnified for T where T: Unsigned + NumCast + Copy + PartialOrd + WrappingAdd + WrappingMul {}
+pub trait UnsignedUnified: Unsigned + NumCast + PartialOrd + Copy + WrappingAdd + WrappingMul + NonZeroRepr {}
+impl<T> UnsignedUnified for T where T: Unsigned + NumCast + Copy + PartialOrd + WrappingAdd + WrappingMul + NonZeroRepr {}
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+mod nonzero {
+    use std::fmt::Debug;
+    use std::hash::Hash;
+    use std::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize};
+
+    // Maps each unsigned primitive to its NonZero twin, so the wrap can be
+    // stored with a niche.
+    pub trait NonZeroRepr: Copy {
+        type NonZero: Copy + Debug + Hash + Eq;
+
+        fn to_nonzero(self) -> Option<Self::NonZero>;
+        fn from_nonzero(nonzero: Self::NonZero) -> Self;
+    }
+
+    macro_rules! impl_nonzero_repr {
+        ($($t:ty => $nonzero:ty),*) => {
+            $(
+                impl NonZeroRepr for $t {
+                    type NonZero = $nonzero;
+
+                    fn to_nonzero(self) -> Option<Self::NonZero> {
+                        <$nonzero>::new(self)
+                    }
+
+                    fn from_nonzero(nonzero: Self::NonZero) -> Self {
+                        nonzero.get()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_nonzero_repr!(u8 => NonZeroU8, u16 => NonZeroU16, u32 => NonZeroU32, u64 => NonZeroU64,
+                       u128 => NonZeroU128, usize => NonZeroUsize);
+}
+
+use nonzero::NonZeroRepr;
+
+// Zero of a ring, for types whose wrap is only known at runtime and so can't
+// implement Default.
+pub trait DefaultWithWrap<T> {
+    fn default_with_wrap(wrap: T) -> Self;
+}
+
+// Operations shared by the wrapping integer types, so generic code can take
+// any of them. Every value is below `get_wrap()`.
+pub trait WrapInteger<T: UnsignedUnified>: Copy + Add<T, Output = Self> + Mul<T, Output = Self> + Into<WrapNum<T>> {
+    fn get_value(self) -> T;
+    fn get_wrap(self) -> T;
+    fn successor(self) -> Self;
+    fn wrapping_sub(self, rhs: T) -> Self;
+}
+
+// The wrap is stored as its NonZero twin: a zero wrap is never valid, and the
+// niche makes Option<WrapNum<T>> the same size as WrapNum<T>.
+#[derive(Debug, Clone, Copy)]
 pub struct WrapNum<T: UnsignedUnified> {
     value: T,
-    wrap: T,
+    wrap: T::NonZero,
+}
+
+// Unsigned type that wraps back to 0 when value exceeds `wrap`.
+// `value < wrap` holds at all times: every operation stores the reduced value,
+// so reading it never needs a modulo.
+// When operating with multiple WrapNums, the wrap value of the former is taken.
+impl<T: UnsignedUnified> WrapNum<T> {
+    pub fn new(value: T, wrap: T) -> WrapNum<T> {
+        let nonzero = wrap.to_nonzero().expect("wrap must be nonzero");
+        assert!(value < wrap, "value must be less than wrap");
+
+        WrapNum {
+            value,
+            wrap: nonzero,
+        }
+    }
+
+    // Clamps `value` to wrap - 1 instead of wrapping it, e.g. to cap an index
+    // at the last slot.
+    pub fn new_saturating(value: T, wrap: T) -> WrapNum<T> {
+        let nonzero = wrap.to_nonzero().expect("wrap must be nonzero");
+
+        WrapNum {
+            value: if value < wrap { value } else { wrap - T::one() },
+            wrap: nonzero,
+        }
+    }
+
+    // Reduces `value` modulo `wrap` instead of panicking like `new`, e.g. for
+    // raw values in a data pipeline. Panics only if wrap is 0.
+    pub fn new_reduced(value: T, wrap: T) -> WrapNum<T> {
+        let nonzero = wrap.to_nonzero().expect("wrap must be nonzero");
+
+        WrapNum {
+            value: value % wrap,
+            wrap: nonzero,
+        }
+    }
+
+    // Zero of the ring with the given wrap.
+    pub fn default_with_wrap(wrap: T) -> WrapNum<T> {
+        WrapNum::new(T::zero(), wrap)
+    }
+
+    // Zero of the ring with wrap W. Takes no arguments, so it can be named
+    // where a plain constructor function is expected, e.g. as
+    // `#[serde(default = "...")]` or in `unwrap_or_else`.
+    pub fn default_for_wrap<const W: usize>() -> WrapNum<T> {
+        WrapNum::default_with_wrap(NumCast::from(W).expect("wrap must fit in T"))
+    }
+
+    // Returns None unless wrap is nonzero and value < wrap, e.g. for checking
+    // untrusted input.
+    pub fn try_new(value: T, wrap: T) -> Option<WrapNum<T>> {
+        if wrap != T::zero() && value < wrap {
+            Some(WrapNum::new(value, wrap))
+        } else {
+            None
+        }
+    }
+
+    // `x` reduced modulo `wrap`, for any wrap the u64 may not fit in T.
+    pub fn from_u64_reduced(x: u64, wrap: T) -> WrapNum<T> {
+        WrapNum::from_u128_reduced(x as u128 % wrap.to_u128().unwrap(), wrap)
+    }
+
+    // `x` reduced with the Euclidean remainder, so negatives count down from
+    // the top of the ring: -1 becomes wrap - 1.
+    pub fn from_i64_reduced(x: i64, wrap: T) -> WrapNum<T> {
+        WrapNum::from_u128_reduced(rem_euclid_i128(x as i128, wrap.to_u128().unwrap()), wrap)
+    }
+
+    // `x` rounded down and reduced like `from_i64_reduced`. Returns None for
+    // NaN and infinities. The reduction is exact for any wrap: x's integer
+    // part is split into mantissa * 2^exp and reduced in u128, rather than
+    // modulo `wrap as f64`, which isn't the real wrap above 2^53.
+    pub fn from_f64_reduced(x: f64, wrap: T) -> Option<WrapNum<T>> {
+        if !x.is_finite() {
+            return None;
+        }
+
+        let n = wrap.to_u128().unwrap();
+        let (mantissa, exp, sign) = num::Float::integer_decode(x.floor());
+        let mut r = if exp < 0 {
+            // Integral, so the shift only drops zero bits.
+            (mantissa as u128).checked_shr(exp.unsigned_abs() as u32).unwrap_or(0) % n
+        } else {
+            mantissa as u128 % n
+        };
+
+        for _ in 0..exp.max(0) {
+            r = add_mod(r, r, n);
+        }
+        if sign < 0 {
+            r = sub_mod(0, r, n);
+        }
+
+        Some(WrapNum::from_u128_reduced(r, wrap))
+    }
+
+    // The position `numer / denom` of the way around the ring, i.e.
+    // `numer * wrap / denom` rounded down, so 1/4 of 360 is 90 and 1/3 of
+    // 100 is 33. Fractions of a whole turn or more wrap around. Panics if
+    // denom is 0.
+    pub fn from_fraction(numer: T, denom: T, wrap: T) -> WrapNum<T> {
+        assert!(denom != T::zero(), "denominator must be nonzero");
+
+        let (numer, denom) = (numer.to_u128().unwrap(), denom.to_u128().unwrap());
+
+        WrapNum::from_u128_reduced(mul_div(numer % denom, wrap.to_u128().unwrap(), denom), wrap)
+    }
+
+    // Reduces every value modulo `wrap`.
+    pub fn from_slice(values: &[T], wrap: T) -> Vec<WrapNum<T>> {
+        let nonzero = wrap.to_nonzero().expect("wrap must be nonzero");
+
+        values.iter().map(|&value| WrapNum {
+            value: value % wrap,
+            wrap: nonzero,
+        }).collect()
+    }
+
+    // Hour of a 24-hour clock, reducing `value`.
+    pub fn clock_hour(value: T) -> WrapNum<T> {
+        WrapNum::clock(value, 24)
+    }
+
+    // Minute of an hour, reducing `value`.
+    pub fn clock_minute(value: T) -> WrapNum<T> {
+        WrapNum::clock(value, 60)
+    }
+
+    // Second of a minute, reducing `value`.
+    pub fn clock_second(value: T) -> WrapNum<T> {
+        WrapNum::clock(value, 60)
+    }
+
+    // Whole degrees, reducing `value` into [0, 360). T must hold 360.
+    pub fn degrees(value: T) -> WrapNum<T> {
+        WrapNum::clock(value, 360)
+    }
+
+    pub fn get_value(self) -> T {
+        self.value
+    }
+
+    // Borrowed access to the value, which is always reduced, also available
+    // as `AsRef<T>`. There's deliberately no `Borrow<T>`: Eq and Hash also
+    // cover the wrap, so two keys with the same value but different wraps
+    // would be equal as borrowed values and unequal as WrapNums, which
+    // `Borrow` forbids.
+    pub fn as_value(&self) -> &T {
+        &self.value
+    }
+
+    // Same as `as_value`. Without `Borrow<T>`, a map keyed by WrapNum can't be
+    // queried by the raw value: look it up with a WrapNum key and read the
+    // raw value off the key that's found.
+    pub fn as_value_ref(&self) -> &T {
+        self.as_value()
+    }
+
+    // The value as an index, e.g. `buf[pos.as_usize()]`. Panics if it doesn't
+    // fit in usize. There's no `Deref<Target = T>`, which would make every
+    // method of T callable on a WrapNum and easy to mistake for a ring one.
+    #[inline]
+    pub fn as_usize(&self) -> usize {
+        self.value.to_usize().expect("index must fit in usize")
+    }
+
+    // The value widened to u64, panics if it doesn't fit.
+    #[inline]
+    pub fn as_u64(&self) -> u64 {
+        self.value.to_u64().expect("value must fit in u64")
+    }
+
+    // The stored representation, which is always equal to `get_value()`.
+    pub fn raw_value(self) -> T {
+        self.value
+    }
+
+    // Reduces the stored value below the wrap. Every operation already keeps
+    // it reduced, so this only matters for values built from raw parts.
+    pub fn normalize(&mut self) {
+        self.value = self.value % self.get_wrap();
+    }
+
+    pub fn get_wrap(self) -> T {
+        T::from_nonzero(self.wrap)
+    }
+
+    // Largest value this ring can hold, wrap - 1.
+    pub fn max_value(self) -> T {
+        self.get_wrap() - T::one()
+    }
+
+    // 0 in this ring, e.g. as a clamping bound.
+    pub fn min_in_ring(self) -> WrapNum<T> {
+        self.with_value(T::zero())
+    }
+
+    // wrap - 1 in this ring, e.g. to start a search at the largest index.
+    pub fn max_in_ring(self) -> WrapNum<T> {
+        self.with_value(self.max_value())
+    }
+
+    // Adds `rhs` and also returns how many full wraps occurred, so the carry
+    // can be fed into the next higher unit (e.g. minutes into hours).
+    pub fn add_with_carry(self, rhs: T) -> (WrapNum<T>, T) {
+        let carry = rhs / self.get_wrap();
+        let rhs = rhs % self.get_wrap();
+
+        if rhs >= self.get_wrap() - self.value {
+            (self.with_value(rhs - (self.get_wrap() - self.value)), carry + T::one())
+        } else {
+            (self.with_value(self.value + rhs), carry)
+        }
+    }
+
+    // `add_with_carry` with the carry as a position in an outer ring of
+    // `outer_wrap`, i.e. the cycles completed by this add modulo
+    // `outer_wrap`, for units nested like odometer digits.
+    pub fn add_nested(self, rhs: T, outer_wrap: T) -> (WrapNum<T>, WrapNum<T>) {
+        let (inner, carry) = self.add_with_carry(rhs);
+
+        (inner, WrapNum::new_reduced(carry, outer_wrap))
+    }
+
+    // Indices for linear probing from this position: successive values
+    // around the ring, at most `len` of them and at most one full cycle.
+    pub fn probe_sequence(self, len: usize) -> impl Iterator<Item = usize> {
+        self.into_iter()
+            .take(len)
+            .map(|num| num.as_usize())
+    }
+
+    // The position as an angle in [0, 2π), treating the whole ring as one
+    // turn, so `degrees(180).to_radians()` is π. The result is within a
+    // couple of ulps of the exact angle while the value and wrap are below
+    // 2^53, and loses precision like any u64 to f64 conversion beyond that.
+    pub fn to_radians(self) -> f64 {
+        let turn = self.value.to_f64().unwrap() / self.get_wrap().to_f64().unwrap();
+
+        (turn * std::f64::consts::TAU).min(std::f64::consts::TAU.next_down())
+    }
+
+    // Adds `other` in the larger of the two rings. The `+` operator keeps the
+    // left-hand wrap instead; this suits merging counters where the larger
+    // ring should dominate.
+    pub fn add_max_wrap(self, other: WrapNum<T>) -> WrapNum<T> {
+        let wrap = if self.get_wrap() >= other.get_wrap() { self.get_wrap() } else { other.get_wrap() };
+
+        WrapNum::new(self.value, wrap) + other.value
+    }
+
+    // Number of times the value would wrap if `rhs` were added, i.e.
+    // (value + rhs) / wrap, computed without overflowing T.
+    pub fn wraps_in_add<U: ToPrimitive>(self, rhs: U) -> T {
+        let (rhs, wrap) = (widen_rhs(rhs), self.get_wrap().to_u128().unwrap());
+        let rem = rhs % wrap;
+        let carry = rhs / wrap + (rem >= wrap - self.value.to_u128().unwrap()) as u128;
+
+        NumCast::from(carry).expect("wrap count doesn't fit in the underlying type")
+    }
+
+    // Same value and wrap as `other`, whatever its underlying type. Both are
+    // widened to u128, so no cast can truncate.
+    pub fn same_as<U: UnsignedUnified>(self, other: WrapNum<U>) -> bool {
+        self.value.to_u128() == other.value.to_u128() && self.get_wrap().to_u128() == other.get_wrap().to_u128()
+    }
+
+    // Serial number ordering as in RFC 1982: self is less than `other` when the
+    // forward distance from self to `other` is less than half the wrap, and
+    // greater when it's more. At exactly half the wrap, which only an even
+    // wrap can have, the order is ambiguous and this returns None.
+    pub fn serial_cmp(&self, other: &WrapNum<T>) -> Option<Ordering> {
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let other = other.value.to_u128().unwrap() % wrap;
+        let distance = sub_mod(other, self.value.to_u128().unwrap(), wrap);
+
+        if distance == 0 {
+            return Some(Ordering::Equal);
+        }
+
+        match distance.cmp(&(wrap - distance)) {
+            Ordering::Less => Some(Ordering::Less),
+            Ordering::Greater => Some(Ordering::Greater),
+            Ordering::Equal => None,
+        }
+    }
+
+    // `self * rhs` if the product is below the wrap, or None when it would
+    // wrap, as for CheckedMul.
+    pub fn checked_mul<U: ToPrimitive>(self, rhs: U) -> Option<WrapNum<T>> {
+        self.value_below_wrap(self.value.to_u128().unwrap().checked_mul(widen_rhs(rhs)))
+    }
+
+    // `+` and `*` that return an error where the operators would panic on
+    // converting rhs. The wrap is never 0, so that can't fail here.
+    pub fn try_add<U: ToPrimitive>(self, rhs: U) -> Result<WrapNum<T>, WrapError> {
+        Ok(self + rhs.to_u128().ok_or(WrapError(()))?)
+    }
+
+    pub fn try_mul<U: ToPrimitive>(self, rhs: U) -> Result<WrapNum<T>, WrapError> {
+        Ok(self * rhs.to_u128().ok_or(WrapError(()))?)
+    }
+
+    // Arithmetic that clamps to `max_value()` or 0 instead of wrapping, for
+    // gauges sized to the ring.
+    pub fn saturating_add<U: ToPrimitive>(self, rhs: U) -> WrapNum<T> {
+        self.saturate(self.value.to_u128().unwrap().checked_add(widen_rhs(rhs)))
+    }
+
+    pub fn saturating_sub<U: ToPrimitive>(self, rhs: U) -> WrapNum<T> {
+        self.with_value(NumCast::from(self.value.to_u128().unwrap().saturating_sub(widen_rhs(rhs))).unwrap())
+    }
+
+    pub fn saturating_mul<U: ToPrimitive>(self, rhs: U) -> WrapNum<T> {
+        self.saturate(self.value.to_u128().unwrap().checked_mul(widen_rhs(rhs)))
+    }
+
+    // The value rounded to the nearest multiple of `k`, halves rounding up. A
+    // value that rounds up to the wrap or beyond wraps around, e.g. 19 to the
+    // nearest 5 on wrap 20 is 0. Panics if k is 0.
+    pub fn round_to_multiple(self, k: T) -> WrapNum<T> {
+        assert!(k != T::zero(), "attempt to round to a multiple of zero");
+
+        let (value, k) = (self.value.to_u128().unwrap(), k.to_u128().unwrap());
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let rem = value % k;
+        let rounded = if rem >= k - rem { add_mod(value - rem, k % wrap, wrap) } else { value - rem };
+
+        self.with_value(NumCast::from(rounded).unwrap())
+    }
+
+    // Whether the value lies on the arc from `lo` forward to `hi`, both
+    // included. The arc wraps past the seam when `lo > hi`, e.g. 5..=2 on
+    // wrap 8 is {5, 6, 7, 0, 1, 2}.
+    pub fn contains_in_ring(self, lo: T, hi: T) -> bool {
+        if lo <= hi {
+            lo <= self.value && self.value <= hi
+        } else {
+            lo <= self.value || self.value <= hi
+        }
+    }
+
+    // `serial_cmp` for two positions in the same ring, e.g. wrapping frame
+    // counters: Less when `other` is newer. Where serial_cmp reduces `other`
+    // into this ring, this panics if the wraps differ.
+    pub fn cmp_circular(&self, other: &WrapNum<T>) -> Option<Ordering> {
+        assert!(self.wrap == other.wrap, "wrap values differ");
+
+        self.serial_cmp(other)
+    }
+
+    // Whether self is 1 to `window` steps ahead of `other`, e.g. a sequence
+    // number that's newer but not by more than the receive window. Equal
+    // positions aren't ahead. With `window` at half the wrap this is the
+    // RFC 1982 comparison `serial_gt`, except that the exact half counts.
+    pub fn is_ahead_of(self, other: WrapNum<T>, window: T) -> bool {
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let distance = sub_mod(self.value.to_u128().unwrap(), other.value.to_u128().unwrap() % wrap, wrap);
+
+        distance != 0 && distance <= window.to_u128().unwrap()
+    }
+
+    // Orders by value alone, ignoring the wrap, e.g. to merge counters with
+    // different wraps into one sorted view. Equal values compare Equal even
+    // when the wraps differ, so this is coarser than `==`.
+    pub fn cmp_value(self, other: WrapNum<T>) -> Ordering {
+        self.value.partial_cmp(&other.value).expect("unsigned values are totally ordered")
+    }
+
+    pub fn serial_lt(&self, other: &WrapNum<T>) -> bool {
+        self.serial_cmp(other) == Some(Ordering::Less)
+    }
+
+    pub fn serial_gt(&self, other: &WrapNum<T>) -> bool {
+        self.serial_cmp(other) == Some(Ordering::Greater)
+    }
+
+    // Next value around the ring, wrap - 1 is followed by 0.
+    pub fn successor(self) -> WrapNum<T> {
+        self.add_with_carry(T::one()).0
+    }
+
+    // Previous value around the ring, 0 is preceded by wrap - 1.
+    pub fn predecessor(self) -> WrapNum<T> {
+        if self.value == T::zero() {
+            self.with_value(self.get_wrap() - T::one())
+        } else {
+            self.with_value(self.value - T::one())
+        }
+    }
+
+    // Moves `n` steps forward around the ring.
+    pub fn advance(self, n: T) -> WrapNum<T> {
+        self.add_with_carry(n).0
+    }
+
+    // The position a fraction `t` of the way to `other` along the shorter
+    // arc, rounded to the nearest step. When both arcs are half the ring,
+    // it goes forward. The arc length goes through f64, so it's only exact
+    // below 2^53. Panics unless t is in [0, 1].
+    pub fn lerp(self, other: WrapNum<T>, t: f64) -> WrapNum<T> {
+        assert!((0.0..=1.0).contains(&t), "t must be in [0, 1]");
+
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let forward = sub_mod(other.value.to_u128().unwrap() % wrap, self.value.to_u128().unwrap(), wrap);
+        let backward = (wrap - forward) % wrap;
+        let steps = |arc: u128| -> T { NumCast::from((arc as f64 * t).round() as u128).unwrap() };
+
+        if forward <= backward {
+            self.advance(steps(forward))
+        } else {
+            self.retreat(steps(backward))
+        }
+    }
+
+    // Moves `n` steps backward around the ring.
+    pub fn retreat(self, n: T) -> WrapNum<T> {
+        self.advance(self.with_value(n % self.get_wrap()).neg_wrapping().value)
+    }
+
+    // Additive inverse in the ring, (wrap - value) % wrap.
+    pub fn neg_wrapping(self) -> WrapNum<T> {
+        self.with_value((self.get_wrap() - self.value) % self.get_wrap())
+    }
+
+    // Modular subtraction: goes below 0 by wrapping to wrap - 1, unlike the
+    // `-` operator, which panics on underflow.
+    pub fn wrapping_sub<U: ToPrimitive>(self, rhs: U) -> WrapNum<T> {
+        self + self.with_value(self.rhs_residue(rhs)).neg_wrapping()
+    }
+
+    // value^exp around the ring by square-and-multiply, 0^0 is 1 % wrap.
+    pub fn pow(self, exp: u32) -> WrapNum<T> {
+        self.pow_wide(exp as u128)
+    }
+
+    // In-place versions of the helpers above, for long-lived counters.
+
+    pub fn successor_assign(&mut self) {
+        *self = self.successor();
+    }
+
+    pub fn predecessor_assign(&mut self) {
+        *self = self.predecessor();
+    }
+
+    pub fn advance_assign(&mut self, n: T) {
+        *self = self.advance(n);
+    }
+
+    pub fn retreat_assign(&mut self, n: T) {
+        *self = self.retreat(n);
+    }
+
+    pub fn neg_assign(&mut self) {
+        *self = self.neg_wrapping();
+    }
+
+    pub fn wrapping_sub_assign<U: ToPrimitive>(&mut self, rhs: U) {
+        *self = self.wrapping_sub(rhs);
+    }
+
+    pub fn pow_assign(&mut self, exp: u32) {
+        *self = self.pow(exp);
+    }
+
+    // Multiplicative inverse, if value and wrap are coprime. In the wrap == 1
+    // ring, 0 is its own inverse.
+    pub fn mod_inverse(self) -> Option<WrapNum<T>> {
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let (mut old_r, mut r) = (self.value.to_u128().unwrap(), wrap);
+        // Bezout coefficients of the value, kept reduced modulo wrap.
+        let (mut old_s, mut s) = (1 % wrap, 0);
+
+        while r != 0 {
+            let q = old_r / r;
+            (old_r, r) = (r, old_r - q * r);
+            (old_s, s) = (s, sub_mod(old_s, mul_mod(q % wrap, s, wrap), wrap));
+        }
+
+        if old_r == 1 {
+            Some(self.with_value(NumCast::from(old_s).unwrap()))
+        } else {
+            None
+        }
+    }
+
+    // Modular division, self * rhs^-1. Unlike truncating division this is
+    // consistent with multiplication: `a.div_mod(b).unwrap() * b == a`.
+    // Returns None when `rhs` has no inverse.
+    pub fn div_mod(self, rhs: T) -> Option<WrapNum<T>> {
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let inverse = self.with_value(rhs % self.get_wrap()).mod_inverse()?;
+        let quotient = mul_mod(self.value.to_u128().unwrap(), inverse.value.to_u128().unwrap(), wrap);
+
+        Some(self.with_value(NumCast::from(quotient).unwrap()))
+    }
+
+    // The methods below mirror `num::Integer` on the value. WrapNum can't
+    // implement the trait itself: it needs Zero and One, and a zero or one
+    // can't be built without knowing the wrap. FixedWrap, whose wrap is part
+    // of the type, does implement it. Division truncates the value, so these
+    // are plain integer operations rather than ring ones, and parity isn't
+    // preserved by wrapping when the wrap is odd.
+
+    // Truncating quotient and remainder of the value, panics if rhs is 0.
+    pub fn div_rem<U: ToPrimitive>(self, rhs: U) -> (WrapNum<T>, WrapNum<T>) {
+        let (value, rhs) = (self.value.to_u128().unwrap(), widen_rhs(rhs));
+
+        (self.with_value(NumCast::from(value / rhs).unwrap()), self.with_value(NumCast::from(value % rhs).unwrap()))
+    }
+
+    // Greatest common divisor of the value and `other`, gcd(0, 0) is 0.
+    pub fn gcd<U: ToPrimitive>(self, other: U) -> T {
+        let (mut a, mut b): (T, T) = (self.value, cast_rhs(other));
+
+        while b != T::zero() {
+            (a, b) = (b, a % b);
+        }
+
+        a
+    }
+
+    // Zero and One can't be implemented because `zero()` and `one()` don't
+    // know the wrap, but both elements can be recognized. In the wrap == 1
+    // ring, 0 is both.
+    pub fn is_zero(self) -> bool {
+        self.value == T::zero()
+    }
+
+    pub fn is_one(self) -> bool {
+        self.value == T::one() % self.get_wrap()
+    }
+
+    pub fn is_even(self) -> bool {
+        self.value % (T::one() + T::one()) == T::zero()
+    }
+
+    pub fn is_odd(self) -> bool {
+        !self.is_even()
+    }
+
+    // `value` reduced into a ring with a fixed, named wrap.
+    fn clock(value: T, wrap: u16) -> WrapNum<T> {
+        let wrap: T = NumCast::from(wrap).expect("wrap must fit in T");
+
+        WrapNum::new(value % wrap, wrap)
+    }
+
+    // `value` must already be below `wrap`.
+    fn from_u128_reduced(value: u128, wrap: T) -> WrapNum<T> {
+        WrapNum::new(NumCast::from(value).unwrap(), wrap)
+    }
+
+    fn with_value(self, value: T) -> WrapNum<T> {
+        WrapNum {
+            value,
+            wrap: self.wrap,
+        }
+    }
+
+    fn pow_wide(self, mut exp: u128) -> WrapNum<T> {
+        let mut base = self;
+        let mut result = self.with_value(T::one() % self.get_wrap());
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.with_value(result.mul_widening(base.value));
+            }
+            base = base.with_value(base.mul_widening(base.value));
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    // `value * rhs` reduced into the ring. The product is taken in u128, so
+    // it can't overflow T before the modulo.
+    fn mul_widening(self, rhs: T) -> T {
+        let product = mul_mod(self.value.to_u128().unwrap(), rhs.to_u128().unwrap(), self.get_wrap().to_u128().unwrap());
+
+        NumCast::from(product).unwrap()
+    }
+
+    // `rhs` modulo the wrap, for operations where only its residue matters.
+    fn rhs_residue<U: ToPrimitive>(self, rhs: U) -> T {
+        NumCast::from(widen_rhs(rhs) % self.get_wrap().to_u128().unwrap()).unwrap()
+    }
+
+    // A WrapNum of `value` if it's there and already below the wrap.
+    fn value_below_wrap(self, value: Option<u128>) -> Option<WrapNum<T>> {
+        value
+            .filter(|&value| value < self.get_wrap().to_u128().unwrap())
+            .map(|value| self.with_value(NumCast::from(value).unwrap()))
+    }
+
+    // `value` clamped to `max_value()`, which None stands for as well.
+    fn saturate(self, value: Option<u128>) -> WrapNum<T> {
+        self.value_below_wrap(value).unwrap_or(self.max_in_ring())
+    }
+
+    // `value + rhs` reduced into the ring without overflowing T.
+    fn add_reduced(self, rhs: T) -> T {
+        self.add_with_carry(rhs).0.value
+    }
+}
+
+// Value and wrap as fixed-size byte arrays, for packet formats. Decoding
+// checks the invariant like `try_new`, so bytes from the wire can't build a
+// WrapNum with a zero wrap or a value at or above it.
+impl<T: UnsignedUnified + ToBytes> WrapNum<T> {
+    pub fn to_le_bytes(self) -> (T::Bytes, T::Bytes) {
+        (self.value.to_le_bytes(), self.get_wrap().to_le_bytes())
+    }
+
+    pub fn to_be_bytes(self) -> (T::Bytes, T::Bytes) {
+        (self.value.to_be_bytes(), self.get_wrap().to_be_bytes())
+    }
+
+    pub fn to_ne_bytes(self) -> (T::Bytes, T::Bytes) {
+        (self.value.to_ne_bytes(), self.get_wrap().to_ne_bytes())
+    }
+}
+
+impl<T: UnsignedUnified + FromBytes> WrapNum<T> {
+    pub fn from_le_bytes(value: &T::Bytes, wrap: &T::Bytes) -> Option<WrapNum<T>> {
+        WrapNum::try_new(T::from_le_bytes(value), T::from_le_bytes(wrap))
+    }
+
+    pub fn from_be_bytes(value: &T::Bytes, wrap: &T::Bytes) -> Option<WrapNum<T>> {
+        WrapNum::try_new(T::from_be_bytes(value), T::from_be_bytes(wrap))
+    }
+
+    pub fn from_ne_bytes(value: &T::Bytes, wrap: &T::Bytes) -> Option<WrapNum<T>> {
+        WrapNum::try_new(T::from_ne_bytes(value), T::from_ne_bytes(wrap))
+    }
+}
+
+// Right-hand sides of any `U: ToPrimitive` are converted by these two
+// helpers instead of an unchecked cast into T. Operators that only need the
+// residue (+, *, wrapping_sub) reduce through u128 first, so a rhs too large
+// for T is still well defined: `WrapNum<u32> + u128::MAX` works. The others
+// compare in u128, so `-` reports the underflow and `%` a divisor larger than
+// the value. What can't be converted at all (negative numbers, NaN) panics
+// with a message naming the type that failed.
+pub(crate) fn widen_rhs<U: ToPrimitive>(rhs: U) -> u128 {
+    rhs.to_u128().unwrap_or_else(|| {
+        panic!("right-hand side of type {} is negative or not finite", std::any::type_name::<U>())
+    })
+}
+
+// Exact conversion for results that must be a T, e.g. `gcd`.
+pub(crate) fn cast_rhs<T: UnsignedUnified, U: ToPrimitive>(rhs: U) -> T {
+    NumCast::from(widen_rhs(rhs)).unwrap_or_else(|| {
+        panic!("right-hand side of type {} doesn't fit in {}", std::any::type_name::<U>(), std::any::type_name::<T>())
+    })
 }
 
-// Unsigned type that wraps back to 0 when value exceeds `wrap`.
-// When operating with multiple WrapNums, the wrap value of the former is taken.
-impl<T: UnsignedUnified> WrapNum<T> {
-    pub fn new(value: T, wrap: T) -> WrapNum<T> {
-        assert!(value < wrap);
+impl<T: UnsignedUnified> WrapInteger<T> for WrapNum<T> {
+    fn get_value(self) -> T {
+        WrapNum::get_value(self)
+    }
+
+    fn get_wrap(self) -> T {
+        WrapNum::get_wrap(self)
+    }
+
+    fn successor(self) -> Self {
+        WrapNum::successor(self)
+    }
+
+    fn wrapping_sub(self, rhs: T) -> Self {
+        WrapNum::wrapping_sub(self, rhs)
+    }
+}
+
+impl<T: UnsignedUnified> DefaultWithWrap<T> for WrapNum<T> {
+    fn default_with_wrap(wrap: T) -> Self {
+        WrapNum::default_with_wrap(wrap)
+    }
+}
+
+impl<T: UnsignedUnified> AsRef<T> for WrapNum<T> {
+    fn as_ref(&self) -> &T {
+        self.as_value()
+    }
+}
+
+// Equal when both the canonical value and the wrap are, written out rather
+// than derived so it visibly matches Hash below.
+impl<T: UnsignedUnified> PartialEq for WrapNum<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_value() == other.get_value() && self.get_wrap() == other.get_wrap()
+    }
+}
+
+impl<T: UnsignedUnified> Eq for WrapNum<T> {}
+
+// Hashes the canonical value and the wrap as plain T, the same fields that
+// `==` compares, so equal WrapNums always hash equally.
+impl<T: UnsignedUnified + Hash> Hash for WrapNum<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get_value().hash(state);
+        self.get_wrap().hash(state);
+    }
+}
+
+// Linear order of the values, then of the wraps, so it's total and agrees
+// with `==`. Values are stored reduced, so no reduction is needed first. This
+// is not the circular order of `serial_cmp`: 9 sorts after 0 on wrap 10 even
+// though it's just behind it.
+impl<T: UnsignedUnified + Ord> Ord for WrapNum<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.value, self.get_wrap()).cmp(&(other.value, other.get_wrap()))
+    }
+}
+
+impl<T: UnsignedUnified + Ord> PartialOrd for WrapNum<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compares the canonical value with a plain integer, in either order, so
+// `num < 5u32` and `5u32 > num` both work. The integer isn't reduced: one at
+// or above the wrap is greater than every value in the ring, and a negative
+// one is less. A blanket impl over `U: ToPrimitive` would overlap
+// `WrapNum == WrapNum`, hence one per primitive.
+macro_rules! impl_cmp_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl<T: UnsignedUnified> PartialEq<$t> for WrapNum<T> {
+                fn eq(&self, other: &$t) -> bool {
+                    self.partial_cmp(other) == Some(Ordering::Equal)
+                }
+            }
+
+            impl<T: UnsignedUnified> PartialOrd<$t> for WrapNum<T> {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    match other.to_u128() {
+                        Some(other) => Some(self.value.to_u128().unwrap().cmp(&other)),
+                        None => Some(Ordering::Greater),
+                    }
+                }
+            }
+
+            impl<T: UnsignedUnified> PartialEq<WrapNum<T>> for $t {
+                fn eq(&self, other: &WrapNum<T>) -> bool {
+                    other == self
+                }
+            }
+
+            impl<T: UnsignedUnified> PartialOrd<WrapNum<T>> for $t {
+                fn partial_cmp(&self, other: &WrapNum<T>) -> Option<Ordering> {
+                    other.partial_cmp(self).map(Ordering::reverse)
+                }
+            }
+        )*
+    };
+}
+
+impl_cmp_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// Every conversion goes through the value directly, rather than the trait's
+// defaults via i64/u64, so the full range of a u128 value survives. Each
+// returns None when the value doesn't fit the target type.
+macro_rules! forward_to_primitive {
+    ($($method:ident -> $ty:ty),*) => {
+        $(fn $method(&self) -> Option<$ty> {
+            self.get_value().$method()
+        })*
+    };
+}
+
+pub(crate) use forward_to_primitive;
+
+// Converts the canonical value. Every operator takes `U: ToPrimitive`, so a
+// WrapNum of any underlying type can be the right-hand side and contributes
+// its value, never its wrap.
+impl<T: UnsignedUnified> ToPrimitive for WrapNum<T> {
+    forward_to_primitive!(
+        to_isize -> isize, to_i8 -> i8, to_i16 -> i16, to_i32 -> i32, to_i64 -> i64, to_i128 -> i128,
+        to_usize -> usize, to_u8 -> u8, to_u16 -> u16, to_u32 -> u32, to_u64 -> u64, to_u128 -> u128,
+        // Rounds to the nearest float when the value has more significant bits.
+        to_f32 -> f32, to_f64 -> f64
+    );
+}
+
+// Lets `&WrapNum` be used as the right-hand side of every operator, so all
+// four owned/borrowed combinations of `a + b` work.
+impl<T: UnsignedUnified> ToPrimitive for &WrapNum<T> {
+    forward_to_primitive!(
+        to_isize -> isize, to_i8 -> i8, to_i16 -> i16, to_i32 -> i32, to_i64 -> i64, to_i128 -> i128,
+        to_usize -> usize, to_u8 -> u8, to_u16 -> u16, to_u32 -> u32, to_u64 -> u64, to_u128 -> u128,
+        to_f32 -> f32, to_f64 -> f64
+    );
+}
+
+// Prints the value, honoring width, fill and alignment. The alternate form
+// `{:#}` also prints the wrap, as in `3 mod 6`.
+impl<T: UnsignedUnified + fmt::Display> fmt::Display for WrapNum<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            f.pad(&format!("{} mod {}", self.value, self.get_wrap()))
+        } else {
+            fmt::Display::fmt(&self.value, f)
+        }
+    }
+}
+
+// Radix formatting of the value, with every flag handled by T's own impl.
+macro_rules! impl_fmt_radix {
+    ($($trait:ident),*) => {
+        $(
+            impl<T: UnsignedUnified + fmt::$trait> fmt::$trait for WrapNum<T> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::$trait::fmt(&self.value, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_fmt_radix!(LowerHex, UpperHex, Binary, Octal);
+
+// Error returned when parsing a WrapNum from a string fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseWrapNumError {
+    // Not of the form `value mod wrap`, or not a decimal integer.
+    Syntax,
+    // An integer doesn't fit the underlying type.
+    Overflow,
+    ZeroWrap,
+    ValueNotBelowWrap,
+}
+
+impl fmt::Display for ParseWrapNumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseWrapNumError::Syntax => f.write_str("expected `value mod wrap`"),
+            ParseWrapNumError::Overflow => f.write_str("integer too large for the underlying type"),
+            ParseWrapNumError::ZeroWrap => f.write_str("wrap must be nonzero"),
+            ParseWrapNumError::ValueNotBelowWrap => f.write_str("value must be less than wrap"),
+        }
+    }
+}
+
+impl Error for ParseWrapNumError {}
+
+// Error returned by the `try_` operations when the right-hand side has no
+// unsigned value, e.g. it's negative or NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrapError(());
+
+impl fmt::Display for WrapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("right-hand side is negative or not finite")
+    }
+}
+
+impl Error for WrapError {}
+
+// Parses `value mod wrap`, the alternate Display form, and also accepts
+// `value % wrap` and `value / wrap`. Whitespace around each part is ignored.
+impl<T: UnsignedUnified> FromStr for WrapNum<T> {
+    type Err = ParseWrapNumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_part<T: UnsignedUnified>(part: &str) -> Result<T, ParseWrapNumError> {
+            let part = part.trim();
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseWrapNumError::Syntax);
+            }
+
+            part.parse::<u128>().ok()
+                .and_then(NumCast::from)
+                .ok_or(ParseWrapNumError::Overflow)
+        }
+
+        let (value, wrap) = ["mod", "%", "/"].iter()
+            .find_map(|sep| s.split_once(sep))
+            .ok_or(ParseWrapNumError::Syntax)?;
+        let (value, wrap): (T, T) = (parse_part(value)?, parse_part(wrap)?);
+
+        if wrap == T::zero() {
+            Err(ParseWrapNumError::ZeroWrap)
+        } else if value >= wrap {
+            Err(ParseWrapNumError::ValueNotBelowWrap)
+        } else {
+            Ok(WrapNum::new(value, wrap))
+        }
+    }
+}
+
+// Error returned when the canonical value doesn't fit the target integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromWrapNumError(());
+
+impl fmt::Display for TryFromWrapNumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("wrapped value out of range for the target integer type")
+    }
+}
+
+impl Error for TryFromWrapNumError {}
+
+// Into the underlying type itself the conversion can't fail, so it's a From
+// yielding the canonical value, as in `let x: u32 = num.into();`. Coherence
+// rules out a blanket impl over T, hence one per primitive.
+macro_rules! impl_from_wrap_num {
+    ($($t:ty),*) => {
+        $(
+            impl From<WrapNum<$t>> for $t {
+                fn from(num: WrapNum<$t>) -> Self {
+                    num.get_value()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_wrap_num!(u8, u16, u32, u64, u128, usize);
+
+// Every other source type may not fit, so converting from it is a TryFrom.
+macro_rules! impl_try_from_wrap_num {
+    ($t:ty => $($from:ty),*) => {
+        $(
+            impl TryFrom<WrapNum<$from>> for $t {
+                type Error = TryFromWrapNumError;
+
+                fn try_from(num: WrapNum<$from>) -> Result<Self, Self::Error> {
+                    <$t as NumCast>::from(num.get_value()).ok_or(TryFromWrapNumError(()))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_wrap_num!(u8 => u16, u32, u64, u128, usize);
+impl_try_from_wrap_num!(u16 => u8, u32, u64, u128, usize);
+impl_try_from_wrap_num!(u32 => u8, u16, u64, u128, usize);
+impl_try_from_wrap_num!(u64 => u8, u16, u32, u128, usize);
+impl_try_from_wrap_num!(u128 => u8, u16, u32, u64, usize);
+impl_try_from_wrap_num!(usize => u8, u16, u32, u64, u128);
+impl_try_from_wrap_num!(i8 => u8, u16, u32, u64, u128, usize);
+impl_try_from_wrap_num!(i16 => u8, u16, u32, u64, u128, usize);
+impl_try_from_wrap_num!(i32 => u8, u16, u32, u64, u128, usize);
+impl_try_from_wrap_num!(i64 => u8, u16, u32, u64, u128, usize);
+impl_try_from_wrap_num!(i128 => u8, u16, u32, u64, u128, usize);
+impl_try_from_wrap_num!(isize => u8, u16, u32, u64, u128, usize);
+
+// Walks the whole ring once, starting at the current value: yields `wrap`
+// items and stops just before returning to the start.
+impl<T: UnsignedUnified> IntoIterator for WrapNum<T> {
+    type Item = WrapNum<T>;
+    type IntoIter = RingIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RingIter {
+            next: self,
+            remaining: self.get_wrap(),
+        }
+    }
+}
+
+// Iterator over a full cycle of the ring, created by `WrapNum::into_iter`.
+#[derive(Debug, Clone)]
+pub struct RingIter<T: UnsignedUnified> {
+    next: WrapNum<T>,
+    remaining: T,
+}
+
+impl<T: UnsignedUnified> Iterator for RingIter<T> {
+    type Item = WrapNum<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == T::zero() {
+            return None;
+        }
+
+        let num = self.next;
+        self.next = num.successor();
+        self.remaining = self.remaining - T::one();
+
+        Some(num)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining.to_usize() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Add<U> for WrapNum<T> {
+    type Output = Self;
+
+    fn add(self, rhs: U) -> Self::Output {
+        self.with_value(self.add_reduced(self.rhs_residue(rhs)))
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> AddAssign<U> for WrapNum<T> {
+    fn add_assign(&mut self, rhs: U) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Sub<U> for WrapNum<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: U) -> Self::Output {
+        let rhs = widen_rhs(rhs);
+        assert!(rhs <= self.value.to_u128().unwrap(), "attempt to subtract with overflow");
+
+        self.with_value(self.value - NumCast::from(rhs).unwrap())
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> SubAssign<U> for WrapNum<T> {
+    fn sub_assign(&mut self, rhs: U) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Mul<U> for WrapNum<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        self.with_value(self.mul_widening(self.rhs_residue(rhs)))
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> MulAssign<U> for WrapNum<T> {
+    fn mul_assign(&mut self, rhs: U) {
+        *self = *self * rhs;
+    }
+}
+
+// Truncating division of the value, like `%` and FixedWrap's `/`, so
+// `a / b * b + a % b == a`. Panics if rhs is 0. Modular division, by the
+// inverse of rhs, is only available as `div_mod`.
+impl<T: UnsignedUnified, U: ToPrimitive> Div<U> for WrapNum<T> {
+    type Output = Self;
+
+    fn div(self, rhs: U) -> Self::Output {
+        let rhs = widen_rhs(rhs);
+        assert!(rhs != 0, "attempt to divide by zero");
+
+        self.with_value(NumCast::from(self.value.to_u128().unwrap() / rhs).unwrap())
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> DivAssign<U> for WrapNum<T> {
+    fn div_assign(&mut self, rhs: U) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Rem<U> for WrapNum<T> {
+    type Output = Self;
+
+    fn rem(self, rhs: U) -> Self::Output {
+        let rhs = widen_rhs(rhs);
+        assert!(rhs != 0, "attempt to calculate the remainder with a divisor of zero");
+
+        self.with_value(NumCast::from(self.value.to_u128().unwrap() % rhs).unwrap())
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> RemAssign<U> for WrapNum<T> {
+    fn rem_assign(&mut self, rhs: U) {
+        *self = *self % rhs;
+    }
+}
+
+impl<T: UnsignedUnified> Neg for WrapNum<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.neg_wrapping()
+    }
+}
+
+// The value mirrored within the ring, `wrap - 1 - value`, so
+// `!WrapNum::new(0, 6)` is 5. This is bitwise `!` when the wrap is a power
+// of two, and differs from `-`, which maps 0 to 0.
+impl<T: UnsignedUnified> Not for WrapNum<T> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        self.with_value(self.max_value() - self.value)
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Add<U> for &WrapNum<T> {
+    type Output = WrapNum<T>;
+
+    fn add(self, rhs: U) -> Self::Output {
+        *self + rhs
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Sub<U> for &WrapNum<T> {
+    type Output = WrapNum<T>;
+
+    fn sub(self, rhs: U) -> Self::Output {
+        *self - rhs
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Mul<U> for &WrapNum<T> {
+    type Output = WrapNum<T>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Div<U> for &WrapNum<T> {
+    type Output = WrapNum<T>;
+
+    fn div(self, rhs: U) -> Self::Output {
+        *self / rhs
+    }
+}
+
+impl<T: UnsignedUnified, U: ToPrimitive> Rem<U> for &WrapNum<T> {
+    type Output = WrapNum<T>;
+
+    fn rem(self, rhs: U) -> Self::Output {
+        *self % rhs
+    }
+}
+
+// Wrapping at the modulus, which is what every WrapNum operator already does
+// apart from `-`. WrappingSub gives trait-visible modular subtraction.
+impl<T: UnsignedUnified> WrappingAdd for WrapNum<T> {
+    fn wrapping_add(&self, v: &Self) -> Self {
+        *self + v
+    }
+}
+
+impl<T: UnsignedUnified> WrappingSub for WrapNum<T> {
+    fn wrapping_sub(&self, v: &Self) -> Self {
+        WrapNum::wrapping_sub(*self, v)
+    }
+}
+
+impl<T: UnsignedUnified> WrappingMul for WrapNum<T> {
+    fn wrapping_mul(&self, v: &Self) -> Self {
+        *self * v
+    }
+}
+
+impl<T: UnsignedUnified> SaturatingAdd for WrapNum<T> {
+    fn saturating_add(&self, v: &Self) -> Self {
+        WrapNum::saturating_add(*self, v)
+    }
+}
+
+impl<T: UnsignedUnified> SaturatingSub for WrapNum<T> {
+    fn saturating_sub(&self, v: &Self) -> Self {
+        WrapNum::saturating_sub(*self, v)
+    }
+}
+
+impl<T: UnsignedUnified> SaturatingMul for WrapNum<T> {
+    fn saturating_mul(&self, v: &Self) -> Self {
+        WrapNum::saturating_mul(*self, v)
+    }
+}
+
+// Exponentiation as `WrapNum::pow`. A WrapNum exponent contributes its value.
+impl<T: UnsignedUnified> Pow<u32> for WrapNum<T> {
+    type Output = Self;
+
+    fn pow(self, exp: u32) -> Self::Output {
+        self.pow_wide(exp as u128)
+    }
+}
+
+impl<T: UnsignedUnified> Pow<u64> for WrapNum<T> {
+    type Output = Self;
+
+    fn pow(self, exp: u64) -> Self::Output {
+        self.pow_wide(exp as u128)
+    }
+}
+
+impl<T: UnsignedUnified> Pow<WrapNum<T>> for WrapNum<T> {
+    type Output = Self;
+
+    fn pow(self, exp: WrapNum<T>) -> Self::Output {
+        self.pow_wide(exp.value.to_u128().unwrap())
+    }
+}
+
+// `self * a + b` in one pass over u128, without building the intermediate
+// product. Matches `(self * a) + b` for any right-hand sides.
+impl<T: UnsignedUnified, A: ToPrimitive, B: ToPrimitive> MulAdd<A, B> for WrapNum<T> {
+    type Output = Self;
+
+    fn mul_add(self, a: A, b: B) -> Self::Output {
+        let wrap = self.get_wrap().to_u128().unwrap();
+        let product = mul_mod(self.value.to_u128().unwrap(), widen_rhs(a) % wrap, wrap);
+
+        self.with_value(NumCast::from(add_mod(product, widen_rhs(b) % wrap, wrap)).unwrap())
+    }
+}
+
+impl<T: UnsignedUnified, A: ToPrimitive, B: ToPrimitive> MulAddAssign<A, B> for WrapNum<T> {
+    fn mul_add_assign(&mut self, a: A, b: B) {
+        *self = self.mul_add(a, b);
+    }
+}
+
+// The multiplicative inverse, panicking when the value isn't a unit, i.e.
+// shares a factor with the wrap. Use `mod_inverse` to get None instead.
+impl<T: UnsignedUnified> Inv for WrapNum<T> {
+    type Output = Self;
+
+    fn inv(self) -> Self::Output {
+        self.mod_inverse().expect("value has no inverse modulo the wrap")
+    }
+}
+
+// Euclidean division of the residues, which for unsigned values is plain
+// truncating division. Both results stay in the left operand's ring. Panics
+// if `v` is 0; CheckedEuclid returns None instead.
+impl<T: UnsignedUnified> Euclid for WrapNum<T> {
+    fn div_euclid(&self, v: &Self) -> Self {
+        self.checked_div_euclid(v).expect("attempt to divide by zero")
+    }
+
+    fn rem_euclid(&self, v: &Self) -> Self {
+        self.checked_rem_euclid(v).expect("attempt to calculate the remainder with a divisor of zero")
+    }
+}
+
+impl<T: UnsignedUnified> CheckedEuclid for WrapNum<T> {
+    fn checked_div_euclid(&self, v: &Self) -> Option<Self> {
+        (!v.is_zero()).then(|| self.with_value(self.value / v.value))
+    }
+
+    fn checked_rem_euclid(&self, v: &Self) -> Option<Self> {
+        (!v.is_zero()).then(|| self.with_value(self.value % v.value))
+    }
+}
+
+// The checked traits return None where the result would wrap past the
+// modulus instead of being reduced, so exhausting a counter is detectable.
+// As with the operators, only the value of `v` is used.
+impl<T: UnsignedUnified> CheckedAdd for WrapNum<T> {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        self.value_below_wrap(self.value.to_u128().unwrap().checked_add(v.value.to_u128().unwrap()))
+    }
+}
+
+impl<T: UnsignedUnified> CheckedSub for WrapNum<T> {
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        self.value_below_wrap(self.value.to_u128().unwrap().checked_sub(v.value.to_u128().unwrap()))
+    }
+}
+
+impl<T: UnsignedUnified> CheckedMul for WrapNum<T> {
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        WrapNum::checked_mul(*self, v)
+    }
+}
+
+// The checked form of the truncating `/`, so generic code gets the same
+// quotient either way: None only when `v` is zero. Modular division, which
+// fails for a `v` with no inverse, is `div_mod`.
+impl<T: UnsignedUnified> CheckedDiv for WrapNum<T> {
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        self.checked_div_euclid(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_wrapnum_nowrap() {
+        let num1 = WrapNum::new(2u32, 6u32);
+        let num2 = WrapNum::new(2u32, 5u32);
+
+        let num3 = num1 + num2;
+
+        assert_eq!(num3, 4u32);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn add_wrapnum_wrap() {
+        let num1 = WrapNum::new(3u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        let num3 = num1 + num2;
+
+        assert_eq!(num3, 1u32);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn add_u32_wrap() {
+        let num1 = WrapNum::new(3u32, 6u32);
+        let num2 = 7u32;
+
+        let num3 = num1 + num2;
+
+        assert_eq!(num3, 4u32);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn sub_u32() {
+        let num1 = WrapNum::new(5u32, 6u32);
+        let num2 = 2u32;
+
+        let num3 = num1 - num2;
+
+        assert_eq!(num3, 3u32);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sub_u32_overflow() {
+        let num1 = WrapNum::new(5u32, 7u32);
+        let num2 = 6u32;
+
+        let _ = num1 - num2;
+    }
+
+    #[test]
+    fn add_assign_wrapnum_wrap() {
+        let mut num1 = WrapNum::new(3u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        num1 += num2;
+
+        assert_eq!(num1.get_value(), 1);
+        assert_eq!(num1.get_wrap(), 6);
+    }
+
+    #[test]
+    fn mul_wrapnum_wrap() {
+        let num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        let num3 = num1 * num2;
+
+        assert_eq!(num3, 4u32);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn mul_assign_wrapnum_wrap() {
+        let mut num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        num1 *= num2;
+
+        assert_eq!(num1.get_value(), 4);
+        assert_eq!(num1.get_wrap(), 6);
+    }
+
+    #[test]
+    fn ops_different_types() {
+        let mut num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u16, 5u16);
+
+        num1 *= num2;
+
+        assert_eq!(num1.get_value(), 4);
+        assert_eq!(num1.get_wrap(), 6);
+    }
+
+    #[test]
+    fn rem_u32() {
+        let num1 = WrapNum::new(5u32, 6u32);
+        let num2 = 2u32;
+
+        let num3 = num1 % num2;
+
+        assert_eq!(num3, 1u32);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn rem_assign_u32() {
+        let mut num1 = WrapNum::new(9u32, 10u32);
+        let num2 = 5u32;
+
+        num1 %= num2;
+
+        assert_eq!(num1.get_value(), 4);
+        assert_eq!(num1.get_wrap(), 10);
+    }
+
+    #[test]
+    fn hash_eq() {
+        use std::hash::{Hasher, DefaultHasher};
+
+        fn calculate_hash<T: Hash>(t: &T) -> u64 {
+            let mut s = DefaultHasher::new();
+            t.hash(&mut s);
+            s.finish()
+        }
+
+        let num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u32, 6u32);
+
+        assert_eq!(calculate_hash(&num1), calculate_hash(&num2));
+    }
+
+    #[test]
+    fn hash_ne() {
+        use std::hash::{Hasher, DefaultHasher};
+
+        fn calculate_hash<T: Hash>(t: &T) -> u64 {
+            let mut s = DefaultHasher::new();
+            t.hash(&mut s);
+            s.finish()
+        }
+
+        let num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        assert_ne!(calculate_hash(&num1), calculate_hash(&num2));
+    }
+
+    #[test]
+    fn eq() {
+        let num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u32, 6u32);
+
+        assert!(num1 == num2);
+    }
+
+    #[test]
+    fn ne() {
+        let num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        assert!(num1 != num2);
+    }
+
+    #[test]
+    fn add_with_carry_minutes_carry_one() {
+        let minutes = WrapNum::new(45u32, 60u32);
+        let hours = WrapNum::new(10u32, 24u32);
+
+        let (minutes, carry) = minutes.add_with_carry(20);
+        let (hours, _) = hours.add_with_carry(carry);
+
+        assert_eq!(minutes.get_value(), 5);
+        assert_eq!(carry, 1);
+        assert_eq!(hours.get_value(), 11);
+    }
+
+    #[test]
+    fn add_with_carry_minutes_carry_two() {
+        let minutes = WrapNum::new(50u32, 60u32);
+
+        let (minutes, carry) = minutes.add_with_carry(75);
+
+        assert_eq!(minutes.get_value(), 5);
+        assert_eq!(minutes.get_wrap(), 60);
+        assert_eq!(carry, 2);
+    }
+
+    #[test]
+    fn add_with_carry_no_wrap() {
+        let minutes = WrapNum::new(10u32, 60u32);
+
+        let (minutes, carry) = minutes.add_with_carry(49);
+
+        assert_eq!(minutes.get_value(), 59);
+        assert_eq!(carry, 0);
+    }
+
+    #[test]
+    fn try_from_fits() {
+        let num = WrapNum::new(200u32, 1000u32);
+
+        let value = u8::try_from(num);
+
+        assert_eq!(value, Ok(200u8));
+    }
+
+    #[test]
+    fn try_from_out_of_range() {
+        let num = WrapNum::new(256u32, 1000u32);
+
+        let value = u8::try_from(num);
+
+        assert_eq!(value, Err(TryFromWrapNumError(())));
+    }
+
+    #[test]
+    fn into_underlying_type() {
+        let num = WrapNum::new(250u32, 300u32) + 100u32;
+
+        let value: u32 = num.into();
+
+        assert_eq!(value, 50);
+        let wide: u128 = WrapNum::new(u128::MAX - 1, u128::MAX).into();
+        assert_eq!(wide, u128::MAX - 1);
+        assert_eq!(i8::try_from(WrapNum::new(200u8, 255u8)), Err(TryFromWrapNumError(())));
+    }
+
+    #[test]
+    fn into_primitive_widening_exact_narrowing() {
+        let num = WrapNum::new(200u8, 255u8) + 100u8;
+
+        let exact: u8 = num.into();
+        let wide: Result<u64, _> = num.try_into();
+        let signed = i16::try_from(num);
+        let narrow = i8::try_from(WrapNum::new(200u16, 300u16));
+
+        assert_eq!(exact, 45);
+        assert_eq!(wide, Ok(45));
+        assert_eq!(signed, Ok(45));
+        assert_eq!(narrow, Err(TryFromWrapNumError(())));
+    }
+
+    #[test]
+    fn try_from_uses_canonical_value() {
+        let num = WrapNum::new(250u32, 300u32) + 100u32;
+
+        let value: Result<u8, _> = num.try_into();
+
+        assert_eq!(value, Ok(50u8));
+    }
+
+    #[test]
+    fn contains_in_ring() {
+        let inside = |lo, hi| (0..8u8).filter(|&v| WrapNum::new(v, 8).contains_in_ring(lo, hi)).collect::<Vec<_>>();
+
+        assert_eq!(inside(2, 5), [2, 3, 4, 5]);
+        assert_eq!(inside(5, 2), [0, 1, 2, 5, 6, 7]);
+        assert_eq!(inside(3, 3), [3]);
+        assert_eq!(inside(0, 7), (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn normalize() {
+        let mut num = WrapNum::new(0u8, 200u8);
+        num.value = 250;
+
+        num.normalize();
+        assert_eq!(num, WrapNum::new(50, 200));
+
+        num.normalize();
+        assert_eq!(num.raw_value(), 50);
+    }
+
+    #[test]
+    fn raw_value_is_canonical_after_ops() {
+        let mut num = WrapNum::new(5u8, 200u8);
+
+        for rhs in [150u8, 199, 255, 3] {
+            num += rhs;
+            assert_eq!(num.raw_value(), num.get_value());
+            assert!(num.raw_value() < num.get_wrap());
+
+            num *= rhs;
+            assert_eq!(num.raw_value(), num.get_value());
+            assert!(num.raw_value() < num.get_wrap());
+
+            num %= rhs;
+            assert_eq!(num.raw_value(), num.get_value());
+
+            num = num - num.get_value() / 2;
+            assert_eq!(num.raw_value(), num.get_value());
+        }
+    }
+
+    #[test]
+    fn add_near_type_max() {
+        let num = WrapNum::new(150u8, 200u8);
+
+        let num = num + 150u8;
+
+        assert_eq!(num.raw_value(), 100);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn add_ref_ref() {
+        let num1 = WrapNum::new(3u32, 6u32);
+        let num2 = WrapNum::new(4u32, 5u32);
+
+        let num3 = &num1 + &num2;
+
+        assert_eq!(num3.get_value(), 1);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ops_ref_combinations() {
+        let num1 = WrapNum::new(5u32, 6u32);
+        let num2 = WrapNum::new(2u32, 5u32);
+
+        assert_eq!((num1 + &num2).get_value(), 1);
+        assert_eq!((&num1 + num2).get_value(), 1);
+        assert_eq!((&num1 - &num2).get_value(), 3);
+        assert_eq!((&num1 * &num2).get_value(), 4);
+        assert_eq!((&num1 % &num2).get_value(), 1);
+        assert_eq!((&num1 + 3u32).get_value(), 2);
+    }
+
+    #[test]
+    fn assign_ops_ref() {
+        let mut num1 = WrapNum::new(5u32, 6u32);
+        let num2 = WrapNum::new(2u32, 5u32);
+
+        num1 += &num2;
+        assert_eq!(num1.get_value(), 1);
+
+        num1 *= &num2;
+        assert_eq!(num1.get_value(), 2);
+
+        num1 -= &num2;
+        assert_eq!(num1.get_value(), 0);
+
+        num1 += &num2;
+        num1 %= &num2;
+        assert_eq!(num1.get_value(), 0);
+    }
+
+    #[test]
+    fn sub_wrapnum_different_types() {
+        let num1 = WrapNum::new(5u32, 6u32);
+        let num2 = WrapNum::new(3u16, 4u16);
+
+        let num3 = num1 - num2;
+
+        assert_eq!(num3.get_value(), 2);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn sub_assign_wrapnum_different_types() {
+        let mut num1 = WrapNum::new(5u8, 6u8);
+        let num2 = WrapNum::new(4u64, 9u64) + 6u64;
+
+        num1 -= num2;
+
+        assert_eq!(num1.get_value(), 4);
+        assert_eq!(num1.get_wrap(), 6);
+    }
+
+    #[test]
+    fn rem_wrapnum_different_types() {
+        let mut num1 = WrapNum::new(9u32, 10u32);
+        let num2 = WrapNum::new(4u16, 5u16);
+
+        assert_eq!((num1 % num2).get_value(), 1);
+
+        num1 %= num2;
+
+        assert_eq!(num1.get_value(), 1);
+        assert_eq!(num1.get_wrap(), 10);
+    }
+
+    #[test]
+    fn crate_root_reexport_is_same_type() {
+        let num: crate::WrapNum<u32> = WrapNum::new(3u32, 6u32);
+
+        let num: WrapNum<u32> = num + 4u32;
+
+        assert_eq!(num.get_value(), 1);
+    }
+
+    #[test]
+    fn add_is_modular_not_type_wrapping() {
+        let num = WrapNum::new(250u8, 251u8);
+
+        let num = num + 255u8;
+
+        assert_eq!(num.get_value(), 3);
+    }
+
+    #[test]
+    fn mul_is_modular() {
+        let num = WrapNum::new(11u32, 13u32);
+
+        let num = num * 12u32;
+
+        assert_eq!(num.get_value(), 11 * 12 % 13);
+    }
+
+    #[test]
+    fn as_value_ref_of_found_map_key() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert(WrapNum::new(4u32, 6u32), "four");
+        map.insert(WrapNum::new(5u32, 6u32), "five");
+
+        let (key, value) = map.get_key_value(&WrapNum::new(4u32, 6u32)).unwrap();
+
+        assert_eq!((*key.as_value_ref(), *value), (4, "four"));
+        assert_eq!(map.get(&WrapNum::new(4u32, 7u32)), None);
+        assert_eq!(*(WrapNum::new(3u32, 6u32) + 4u32).as_value_ref(), 1);
+    }
+
+    #[test]
+    fn neg_operator() {
+        let num = WrapNum::new(2u32, 6u32);
+
+        let num = -num;
+
+        assert_eq!(num.get_value(), 4);
+        assert_eq!(num.get_wrap(), 6);
+        assert_eq!((num + 2u32).get_value(), 0);
+    }
+
+    #[test]
+    fn neg_zero() {
+        assert_eq!((-WrapNum::new(0u32, 6u32)).get_value(), 0);
+    }
+
+    #[test]
+    fn neg_agrees_with_neg_wrapping() {
+        for value in 0..6u8 {
+            let num = WrapNum::new(value, 6u8);
+
+            assert_eq!(-num, num.neg_wrapping());
+        }
+    }
+
+    #[test]
+    fn successor_wraps_at_top() {
+        let num = WrapNum::new(4u32, 6u32);
+
+        assert_eq!(num.successor().get_value(), 5);
+        assert_eq!(num.successor().successor().get_value(), 0);
+        assert_eq!(num.successor().get_wrap(), 6);
+    }
+
+    #[test]
+    fn predecessor_wraps_at_zero() {
+        let num = WrapNum::new(1u32, 6u32);
+
+        assert_eq!(num.predecessor().get_value(), 0);
+        assert_eq!(num.predecessor().predecessor().get_value(), 5);
+        assert_eq!(num.predecessor().successor(), num);
+    }
+
+    #[test]
+    fn successor_predecessor_wrap_one() {
+        let num = WrapNum::new(0u8, 1u8);
+
+        assert_eq!(num.successor(), num);
+        assert_eq!(num.predecessor(), num);
+    }
+
+    #[test]
+    fn max_value() {
+        assert_eq!(WrapNum::new(0u32, 6u32).max_value(), 5);
+        assert_eq!(WrapNum::new(0u8, 1u8).max_value(), 0);
+        assert_eq!(WrapNum::new(7u64, u64::MAX).max_value(), u64::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn new_zero_wrap() {
+        let _ = WrapNum::new(0u32, 0u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "value must be less than wrap")]
+    fn new_value_at_wrap() {
+        let _ = WrapNum::new(6u32, 6u32);
+    }
+
+    #[test]
+    fn option_niche() {
+        use std::mem::size_of;
+
+        assert_eq!(size_of::<Option<WrapNum<u32>>>(), size_of::<WrapNum<u32>>());
+        assert_eq!(size_of::<Option<WrapNum<u64>>>(), size_of::<WrapNum<u64>>());
+        assert_eq!(size_of::<Option<WrapNum<u8>>>(), 2);
+    }
+
+    #[test]
+    fn wrapping_sub_u32() {
+        let num1 = WrapNum::new(5u32, 6u32);
+        let num2 = 2u32;
+
+        let num3 = num1.wrapping_sub(num2);
+
+        assert_eq!(num3.get_value(), 3);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn wrapping_sub_u32_wrap() {
+        let num1 = WrapNum::new(5u32, 7u32);
+        let num2 = 6u32;
+
+        let num3 = num1.wrapping_sub(num2);
+
+        assert_eq!(num3.get_value(), 6);
+        assert_eq!(num3.get_wrap(), 7);
+    }
+
+    #[test]
+    fn wrapping_sub_multiple_laps() {
+        let num1 = WrapNum::new(1u8, 7u8);
+
+        let num3 = num1.wrapping_sub(255u8);
+
+        assert_eq!(num3.get_value(), 5);
+    }
+
+    #[test]
+    fn wrapping_sub_wrapnum() {
+        let num1 = WrapNum::new(1u32, 6u32);
+        let num2 = WrapNum::new(4u16, 5u16);
+
+        let num3 = num1.wrapping_sub(num2);
+
+        assert_eq!(num3.get_value(), 3);
+        assert_eq!(num3.get_wrap(), 6);
+    }
+
+    #[test]
+    fn advance_multiple_cycles() {
+        let num = WrapNum::new(4u32, 6u32);
+
+        assert_eq!(num.advance(1).get_value(), 5);
+        assert_eq!(num.advance(2).get_value(), 0);
+        assert_eq!(num.advance(6 * 3 + 5).get_value(), 3);
+        assert_eq!(num.advance(0), num);
+    }
+
+    #[test]
+    fn retreat_multiple_cycles() {
+        let num = WrapNum::new(1u8, 6u8);
+
+        assert_eq!(num.retreat(1).get_value(), 0);
+        assert_eq!(num.retreat(2).get_value(), 5);
+        assert_eq!(num.retreat(6 * 40 + 3).get_value(), 4);
+        assert_eq!(num.retreat(255).advance(255), num);
+    }
+
+    #[test]
+    fn into_iter_full_cycle() {
+        let num = WrapNum::new(3u32, 5u32);
+
+        let values: Vec<_> = num.into_iter().map(WrapNum::get_value).collect();
+
+        assert_eq!(values, vec![3, 4, 0, 1, 2]);
+    }
+
+    #[test]
+    fn into_iter_distinct_and_complete() {
+        let num = WrapNum::new(200u8, 255u8);
+
+        let values: std::collections::HashSet<_> = num.into_iter().collect();
+
+        assert_eq!(num.into_iter().count(), 255);
+        assert_eq!(values.len(), 255);
+        assert!(num.into_iter().all(|n| n.get_wrap() == 255));
+    }
+
+    #[test]
+    fn into_iter_wrap_one() {
+        let num = WrapNum::new(0u16, 1u16);
+
+        assert_eq!(num.into_iter().collect::<Vec<_>>(), vec![num]);
+    }
+
+    #[test]
+    fn into_iter_for_loop() {
+        let mut seen = Vec::new();
+
+        for num in WrapNum::new(1usize, 3usize) {
+            seen.push(num.get_value());
+        }
+
+        assert_eq!(seen, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn wraps_in_add() {
+        let num = WrapNum::new(0u32, 5u32);
+
+        assert_eq!(num.wraps_in_add(13u8), 2);
+        assert_eq!(num.wraps_in_add(4u8), 0);
+        assert_eq!(WrapNum::new(3u32, 5u32).wraps_in_add(2u8), 1);
+    }
+
+    #[test]
+    fn wraps_in_add_near_type_max() {
+        let num = WrapNum::new(254u8, 255u8);
+
+        assert_eq!(num.wraps_in_add(255u8), 1);
+        assert_eq!(WrapNum::new(0u8, 1u8).wraps_in_add(255u8), 255);
+    }
+
+    // Before 0.2 Eq and Hash saw the unreduced stored value, so these
+    // compared unequal although both are 2 mod 5.
+    #[test]
+    fn equal_residues_from_different_ops_are_equal() {
+        use std::hash::{Hasher, DefaultHasher};
+
+        fn calculate_hash<T: Hash>(t: &T) -> u64 {
+            let mut s = DefaultHasher::new();
+            t.hash(&mut s);
+            s.finish()
+        }
+
+        let num1 = WrapNum::new(4u32, 5u32) + 3u32;
+        let num2 = WrapNum::new(1u32, 5u32) * 12u32;
+        let num3 = WrapNum::new(2u32, 5u32);
+
+        assert_eq!(num1, num2);
+        assert_eq!(num2, num3);
+        assert_eq!(calculate_hash(&num1), calculate_hash(&num2));
+        assert_eq!(calculate_hash(&num2), calculate_hash(&num3));
+    }
+
+    #[test]
+    fn hash_set_dedups_equal_residues() {
+        let start = WrapNum::new(0u8, 7u8);
+
+        let set: std::collections::HashSet<_> = (0..50u8).map(|n| start + n).collect();
+
+        assert_eq!(set.len(), 7);
+    }
+
+    // Before 0.2 the unreduced u8 overflowed: 250 + 10 stored 4, a residue
+    // of 4 mod 251 instead of 9.
+    #[test]
+    fn add_past_type_max_keeps_residue() {
+        let num = WrapNum::new(250u8, 251u8);
+
+        assert_eq!((num + 10u8).get_value(), 9);
+    }
+
+    #[test]
+    fn same_as_across_types() {
+        let num1 = WrapNum::new(300u16, 1000u16);
+
+        assert!(num1.same_as(WrapNum::new(300u32, 1000u32)));
+        assert!(!num1.same_as(WrapNum::new(301u32, 1000u32)));
+        assert!(!num1.same_as(WrapNum::new(300u32, 1001u32)));
+    }
+
+    #[test]
+    fn same_as_no_truncation() {
+        let num1 = WrapNum::new(44u8, 200u8);
+
+        // 65580 truncates to 44 as a u8, but the values differ.
+        assert!(!num1.same_as(WrapNum::new(65580u32, 70000u32)));
+        assert!(!WrapNum::new(44u32, 456u32).same_as(WrapNum::new(44u8, 200u8)));
+    }
+
+    #[test]
+    fn serial_cmp_across_seam() {
+        let num1 = WrapNum::new(250u8, 255u8);
+
+        let num2 = num1 + 10u8;
+
+        assert!(num1.serial_lt(&num2));
+        assert!(num2.serial_gt(&num1));
+        assert!(num2.get_value() < num1.get_value());
+    }
+
+    #[test]
+    fn serial_cmp_even_wrap_half_is_ambiguous() {
+        let num1 = WrapNum::new(1u32, 10u32);
+
+        assert_eq!(num1.serial_cmp(&WrapNum::new(5, 10)), Some(Ordering::Less));
+        assert_eq!(num1.serial_cmp(&WrapNum::new(6, 10)), None);
+        assert_eq!(num1.serial_cmp(&WrapNum::new(7, 10)), Some(Ordering::Greater));
+        assert!(!num1.serial_lt(&WrapNum::new(6, 10)));
+        assert!(!num1.serial_gt(&WrapNum::new(6, 10)));
+    }
+
+    #[test]
+    fn serial_cmp_odd_wrap() {
+        let num1 = WrapNum::new(0u32, 7u32);
+
+        assert_eq!(num1.serial_cmp(&WrapNum::new(3, 7)), Some(Ordering::Less));
+        assert_eq!(num1.serial_cmp(&WrapNum::new(4, 7)), Some(Ordering::Greater));
+        assert_eq!(num1.serial_cmp(&num1), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn serial_cmp_matches_antisymmetry() {
+        for a in 0..12u16 {
+            for b in 0..12u16 {
+                let num1 = WrapNum::new(a, 12);
+                let num2 = WrapNum::new(b, 12);
+
+                assert_eq!(num1.serial_cmp(&num2), num2.serial_cmp(&num1).map(Ordering::reverse));
+            }
+        }
+    }
+
+    #[test]
+    fn serial_cmp_near_type_max() {
+        let num1 = WrapNum::new(u128::MAX - 1, u128::MAX);
+
+        assert!(num1.serial_lt(&WrapNum::new(3, u128::MAX)));
+        assert!(num1.serial_gt(&WrapNum::new(u128::MAX / 2, u128::MAX)));
+    }
+
+    #[test]
+    fn div_mod_prime_wrap() {
+        for a in 0..13u32 {
+            for b in 1..13u32 {
+                let num = WrapNum::new(a, 13u32);
+
+                assert_eq!(num.div_mod(b).unwrap() * b, num);
+            }
+        }
+    }
+
+    #[test]
+    fn div_mod_no_inverse() {
+        let num = WrapNum::new(8u32, 12u32);
+
+        assert_eq!(num.div_mod(4), None);
+        assert_eq!(num.div_mod(0), None);
+        assert_eq!(num.div_mod(5), Some(WrapNum::new(4, 12)));
+        assert_eq!(num.div_mod(17), Some(WrapNum::new(4, 12)));
+    }
+
+    #[test]
+    fn mod_inverse() {
+        assert_eq!(WrapNum::new(3u8, 7u8).mod_inverse(), Some(WrapNum::new(5, 7)));
+        assert_eq!(WrapNum::new(1u8, 7u8).mod_inverse(), Some(WrapNum::new(1, 7)));
+        assert_eq!(WrapNum::new(0u8, 7u8).mod_inverse(), None);
+        assert_eq!(WrapNum::new(6u8, 9u8).mod_inverse(), None);
+        assert_eq!(WrapNum::new(0u8, 1u8).mod_inverse(), Some(WrapNum::new(0, 1)));
+    }
+
+    #[test]
+    fn div_mod_large_prime() {
+        let p = (1u64 << 61) - 1;
+        let num = WrapNum::new(p - 3, p);
+        let b = p - 12345;
+
+        let quotient = num.div_mod(b).unwrap();
+
+        assert_eq!(quotient.get_value() as u128 * b as u128 % p as u128, (p - 3) as u128);
+    }
+
+    #[test]
+    fn mod_inverse_near_u128_max() {
+        let num = WrapNum::new(u128::MAX - 1, u128::MAX);
+
+        // -1 is its own inverse.
+        assert_eq!(num.mod_inverse(), Some(num));
+    }
+
+    #[test]
+    fn from_slice_reduces() {
+        let nums = WrapNum::from_slice(&[0u16, 5, 6, 13, 65535], 6);
+
+        assert_eq!(nums.len(), 5);
+        assert_eq!(nums.iter().map(|num| num.get_value()).collect::<Vec<_>>(), vec![0, 5, 0, 1, 3]);
+        assert!(nums.iter().all(|num| num.get_wrap() == 6));
+    }
+
+    #[test]
+    fn from_slice_empty() {
+        assert!(WrapNum::from_slice(&[], 6u8).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn from_slice_zero_wrap() {
+        WrapNum::from_slice(&[1u32], 0);
+    }
+
+    #[test]
+    fn display() {
+        let num = WrapNum::new(3u32, 6u32);
+
+        assert_eq!(format!("{}", num), "3");
+        assert_eq!(format!("{:>5}", num), "    3");
+        assert_eq!(format!("{:*<4}", num), "3***");
+        assert_eq!(format!("{:03}", num), "003");
+    }
+
+    #[test]
+    fn display_alternate() {
+        let num = WrapNum::new(3u32, 6u32);
+
+        assert_eq!(format!("{:#}", num), "3 mod 6");
+        assert_eq!(format!("{:>#9}", num), "  3 mod 6");
+    }
+
+    #[test]
+    fn display_reduced_value() {
+        let num = WrapNum::new(5u8, 6u8) + 250u8;
+
+        assert_eq!(num.to_string(), "3");
+        assert_eq!(format!("{:#}", num), "3 mod 6");
+    }
+
+    #[test]
+    fn div_rem_consistency() {
+        for a in 0..20u32 {
+            for b in 1..25u32 {
+                let num = WrapNum::new(a, 20u32);
+
+                let (q, r) = num.div_rem(b);
+
+                assert_eq!(q.get_value() * b + r.get_value(), a);
+                assert!(r.get_value() < b);
+                assert_eq!(q.get_wrap(), 20);
+            }
+        }
+    }
+
+    #[test]
+    fn is_even_on_reduced_value() {
+        let num = WrapNum::new(6u8, 7u8) + 2u8;
+
+        assert_eq!(num.get_value(), 1);
+        assert!(num.is_odd());
+        assert!(!num.is_even());
+        assert!(WrapNum::new(0u8, 7u8).is_even());
+    }
+
+    #[test]
+    fn gcd() {
+        let num = WrapNum::new(12u32, 100u32);
+
+        assert_eq!(num.gcd(18u32), 6);
+        assert_eq!(num.gcd(0u32), 12);
+        assert_eq!(WrapNum::new(0u32, 5u32).gcd(0u32), 0);
+        assert_eq!(num.gcd(WrapNum::new(35u32, 50u32)), 1);
+    }
+
+    #[test]
+    fn radix_formatting_matches_value() {
+        let num = WrapNum::new(250u8, 251u8) + 200u8;
+        let value = num.get_value();
+
+        assert_eq!(value, 199);
+        for (formatted, expected) in [
+            (format!("{:x}", num), format!("{:x}", value)),
+            (format!("{:#x}", num), format!("{:#x}", value)),
+            (format!("{:08x}", num), format!("{:08x}", value)),
+            (format!("{:#08x}", num), format!("{:#08x}", value)),
+            (format!("{:X}", num), format!("{:X}", value)),
+            (format!("{:#X}", num), format!("{:#X}", value)),
+            (format!("{:>6X}", num), format!("{:>6X}", value)),
+            (format!("{:b}", num), format!("{:b}", value)),
+            (format!("{:#b}", num), format!("{:#b}", value)),
+            (format!("{:#012b}", num), format!("{:#012b}", value)),
+            (format!("{:o}", num), format!("{:o}", value)),
+            (format!("{:#o}", num), format!("{:#o}", value)),
+            (format!("{:<7o}|", num), format!("{:<7o}|", value)),
+        ] {
+            assert_eq!(formatted, expected);
+        }
+        assert_eq!(format!("{:#06x}", num), "0x00c7");
+    }
+
+    #[test]
+    fn cmp_value_ignores_wrap() {
+        let num1 = WrapNum::new(3u32, 4u32);
+        let num2 = WrapNum::new(3u32, 100u32);
+
+        assert_eq!(num1.cmp_value(num2), Ordering::Equal);
+        assert_ne!(num1, num2);
+        assert_eq!(WrapNum::new(9u32, 10u32).cmp_value(WrapNum::new(50, 60)), Ordering::Less);
+    }
+
+    #[test]
+    fn sort_mixed_wraps_by_value() {
+        let mut nums = [
+            WrapNum::new(7u16, 8),
+            WrapNum::new(2u16, 3),
+            WrapNum::new(40u16, 60),
+            WrapNum::new(0u16, 1),
+            WrapNum::new(5u16, 1000),
+        ];
+
+        nums.sort_by(|a, b| a.cmp_value(*b));
+
+        assert_eq!(nums.iter().map(|num| num.get_value()).collect::<Vec<_>>(), vec![0, 2, 5, 7, 40]);
+    }
+
+    #[test]
+    fn mul_u8_product_over_type_max() {
+        let num = WrapNum::new(199u8, 200u8);
+
+        // 199 * 198 = 39402, far past u8::MAX.
+        assert_eq!((num * 198u8).get_value(), (199 * 198 % 200) as u8);
+    }
+
+    #[test]
+    fn mul_assign_u8_product_over_type_max() {
+        let mut num = WrapNum::new(150u8, 200u8);
+
+        num *= 170u8;
+
+        assert_eq!(num.get_value(), (150 * 170 % 200) as u8);
+    }
+
+    #[test]
+    fn mul_u128_near_type_max() {
+        let num = WrapNum::new(u128::MAX - 1, u128::MAX);
+
+        // (-1) * (-1) = 1.
+        assert_eq!((num * (u128::MAX - 1)).get_value(), 1);
+    }
+
+    #[test]
+    fn pow() {
+        let num = WrapNum::new(3u8, 200u8);
+
+        assert_eq!(num.pow(0).get_value(), 1);
+        assert_eq!(num.pow(1).get_value(), 3);
+        assert_eq!(num.pow(5).get_value(), (243 % 200) as u8);
+        assert_eq!(num.pow(10).get_value(), (59049 % 200) as u8);
+        assert_eq!(WrapNum::new(0u8, 1u8).pow(0).get_value(), 0);
+    }
+
+    #[test]
+    fn pow_fermat() {
+        // Largest prime below 2^32, so every product overflows u32.
+        let p = 4_294_967_291u32;
+
+        for a in [2u32, 3, 65_537, p - 1] {
+            assert_eq!(WrapNum::new(a, p).pow(p - 1).get_value(), 1);
+        }
+    }
+
+    #[test]
+    fn from_str_forms() {
+        assert_eq!("3 mod 6".parse(), Ok(WrapNum::new(3u32, 6u32)));
+        assert_eq!("3%6".parse(), Ok(WrapNum::new(3u32, 6u32)));
+        assert_eq!("3/6".parse(), Ok(WrapNum::new(3u32, 6u32)));
+        assert_eq!("  3 \t%  6\n".parse(), Ok(WrapNum::new(3u32, 6u32)));
+        assert_eq!("3mod6".parse(), Ok(WrapNum::new(3u32, 6u32)));
+    }
+
+    #[test]
+    fn from_str_round_trips_display() {
+        let num = WrapNum::new(250u8, 251u8);
+
+        assert_eq!(format!("{:#}", num).parse(), Ok(num));
+    }
+
+    #[test]
+    fn from_str_errors() {
+        assert_eq!("3".parse::<WrapNum<u32>>(), Err(ParseWrapNumError::Syntax));
+        assert_eq!("3 mod".parse::<WrapNum<u32>>(), Err(ParseWrapNumError::Syntax));
+        assert_eq!("x mod 6".parse::<WrapNum<u32>>(), Err(ParseWrapNumError::Syntax));
+        assert_eq!("-1 mod 6".parse::<WrapNum<u32>>(), Err(ParseWrapNumError::Syntax));
+        assert_eq!("3 mod 0".parse::<WrapNum<u32>>(), Err(ParseWrapNumError::ZeroWrap));
+        assert_eq!("6 mod 6".parse::<WrapNum<u32>>(), Err(ParseWrapNumError::ValueNotBelowWrap));
+    }
+
+    #[test]
+    fn from_str_overflow() {
+        assert_eq!("3 mod 256".parse::<WrapNum<u8>>(), Err(ParseWrapNumError::Overflow));
+        assert_eq!("3 mod 255".parse::<WrapNum<u8>>(), Ok(WrapNum::new(3, 255)));
+        assert_eq!("1 mod 999999999999999999999999999999999999999999".parse::<WrapNum<u128>>(),
+                   Err(ParseWrapNumError::Overflow));
+    }
+
+    #[test]
+    fn clock_constructors() {
+        assert_eq!(WrapNum::clock_hour(26u32).get_value(), 2);
+        assert_eq!(WrapNum::clock_hour(23u8).get_wrap(), 24);
+        assert_eq!(WrapNum::clock_minute(125u16), WrapNum::new(5, 60));
+        assert_eq!(WrapNum::clock_second(59u64), WrapNum::new(59, 60));
+        assert_eq!((WrapNum::clock_second(50u32) + 15u32).get_value(), 5);
+    }
+
+    #[test]
+    fn try_new_rejects_invalid() {
+        assert_eq!(WrapNum::try_new(2u32, 3u32), Some(WrapNum::new(2, 3)));
+        assert_eq!(WrapNum::try_new(10u32, 3u32), None);
+        assert_eq!(WrapNum::try_new(3u32, 3u32), None);
+        assert_eq!(WrapNum::try_new(0u32, 0u32), None);
+    }
+
+    #[test]
+    fn probe_sequence_wraps() {
+        let num = WrapNum::new(6u32, 8u32);
+
+        assert_eq!(num.probe_sequence(4).collect::<Vec<_>>(), vec![6, 7, 0, 1]);
+    }
+
+    #[test]
+    fn probe_sequence_length() {
+        let num = WrapNum::new(3u64, 1 << 40);
+
+        assert_eq!(num.probe_sequence(5).count(), 5);
+        assert_eq!(num.probe_sequence(0).count(), 0);
+        assert_eq!(WrapNum::new(1u8, 3u8).probe_sequence(10).collect::<Vec<_>>(), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn new_saturating() {
+        assert_eq!(WrapNum::new_saturating(100u32, 6u32).get_value(), 5);
+        assert_eq!(WrapNum::new_saturating(6u32, 6u32).get_value(), 5);
+        assert_eq!(WrapNum::new_saturating(4u32, 6u32).get_value(), 4);
+        assert_eq!(WrapNum::new_saturating(255u8, 1u8).get_value(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn new_saturating_zero_wrap() {
+        WrapNum::new_saturating(1u32, 0);
+    }
+
+    #[test]
+    fn hash_is_value_then_wrap() {
+        use std::hash::DefaultHasher;
+
+        fn calculate_hash<T: Hash>(t: &T) -> u64 {
+            let mut s = DefaultHasher::new();
+            t.hash(&mut s);
+            s.finish()
+        }
+
+        let reduced = WrapNum::new(7u32 % 6, 6u32);
+        let via_add = WrapNum::new(5u32, 6u32) + 2u32;
+
+        assert_eq!(calculate_hash(&reduced), calculate_hash(&via_add));
+        assert_eq!(calculate_hash(&reduced), calculate_hash(&(1u32, 6u32)));
+    }
+
+    #[test]
+    fn to_float() {
+        let num = WrapNum::new(180u32, 360u32);
+
+        assert_eq!(num.to_f64(), Some(180.0));
+        assert_eq!(num.to_f32(), Some(180.0));
+        assert_eq!(ToPrimitive::to_f64(&&num), Some(180.0));
+        assert_eq!(num.to_f64().unwrap() / num.get_wrap() as f64, 0.5);
+    }
+
+    #[test]
+    fn to_float_beyond_u64() {
+        let num = WrapNum::new(1u128 << 100, u128::MAX);
+
+        assert_eq!(num.to_f64(), Some(2f64.powi(100)));
+    }
+
+    #[test]
+    fn to_primitive_reports_canonical_value() {
+        // 250 + 10 overflows u8 before reduction, 250 * 200 overflows it too.
+        let num = WrapNum::new(250u8, 251u8) + 10u8;
+        let num2 = WrapNum::new(250u8, 251u8) * 200u8;
+
+        assert_eq!(num.to_u64(), Some(9));
+        assert_eq!(num.to_i64(), Some(9));
+        assert_eq!(num2.to_u64(), Some(250 * 200 % 251));
+        assert_eq!(<u32 as NumCast>::from(num2), Some(250 * 200 % 251));
+    }
+
+    #[test]
+    fn degrees() {
+        assert_eq!(WrapNum::degrees(370u32).get_value(), 10);
+        assert_eq!(WrapNum::degrees(359u16).get_wrap(), 360);
+        assert_eq!((WrapNum::degrees(350u32) + 20u32).get_value(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must fit in T")]
+    fn degrees_u8() {
+        WrapNum::degrees(10u8);
+    }
+
+    #[test]
+    fn to_radians() {
+        use std::f64::consts::{PI, FRAC_PI_2};
+
+        assert!((WrapNum::degrees(180u32).to_radians() - PI).abs() < 1e-15);
+        assert!((WrapNum::degrees(450u32).to_radians() - FRAC_PI_2).abs() < 1e-15);
+        assert_eq!(WrapNum::degrees(0u32).to_radians(), 0.0);
+        assert!(WrapNum::new(u64::MAX - 1, u64::MAX).to_radians() < std::f64::consts::TAU);
+    }
+
+    #[test]
+    fn add_max_wrap() {
+        let num1 = WrapNum::new(4u32, 6u32);
+        let num2 = WrapNum::new(7u32, 10u32);
+
+        assert_eq!(num1.add_max_wrap(num2), WrapNum::new(1, 10));
+        assert_eq!(num2.add_max_wrap(num1), WrapNum::new(1, 10));
+        assert_eq!(num1 + num2, WrapNum::new(5, 6));
+        assert_eq!(num1.add_max_wrap(WrapNum::new(5, 6)), WrapNum::new(3, 6));
+    }
+
+    #[test]
+    fn default_with_wrap() {
+        assert_eq!(WrapNum::default_with_wrap(6u32), WrapNum::new(0, 6));
+        assert_eq!(WrapNum::<u16>::default_for_wrap::<360>(), WrapNum::new(0, 360));
+        assert_eq!("x".parse().unwrap_or_else(|_| WrapNum::<u8>::default_for_wrap::<7>()), WrapNum::new(0, 7));
+    }
+
+    #[test]
+    fn default_with_wrap_trait() {
+        fn zeros<N: DefaultWithWrap<u32>>(wrap: u32, count: usize) -> Vec<N> {
+            (0..count).map(|_| N::default_with_wrap(wrap)).collect()
+        }
+
+        assert_eq!(zeros::<WrapNum<u32>>(5, 2), vec![WrapNum::new(0, 5); 2]);
+    }
+
+    #[test]
+    fn is_zero_and_is_one() {
+        assert!(WrapNum::new(0u32, 6u32).is_zero());
+        assert!((WrapNum::new(5u32, 6u32) + 1u32).is_zero());
+        assert!(WrapNum::new(1u32, 6u32).is_one());
+        assert!(!WrapNum::new(2u32, 6u32).is_one());
+        assert!(WrapNum::new(0u32, 1u32).is_zero());
+        assert!(WrapNum::new(0u32, 1u32).is_one());
+    }
+
+    #[test]
+    fn rem_assign_wrapnum() {
+        let mut num1 = WrapNum::new(17u32, 20u32);
+
+        num1 %= WrapNum::new(5u8, 6u8);
+
+        assert_eq!(num1, WrapNum::new(2, 20));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to calculate the remainder with a divisor of zero")]
+    fn rem_wrapnum_zero() {
+        let _ = WrapNum::new(4u32, 6u32) % (WrapNum::new(5u32, 6u32) + 1u32);
+    }
 
-        WrapNum {
-            value,
-            wrap,
+    #[test]
+    fn assign_variants_match_by_value() {
+        for value in 0..7u8 {
+            let num = WrapNum::new(value, 7u8);
+            let check = |f: fn(&mut WrapNum<u8>), expected: WrapNum<u8>| {
+                let mut num = num;
+                f(&mut num);
+                assert_eq!(num, expected);
+            };
+
+            check(WrapNum::successor_assign, num.successor());
+            check(WrapNum::predecessor_assign, num.predecessor());
+            check(|num| num.advance_assign(200), num.advance(200));
+            check(|num| num.retreat_assign(200), num.retreat(200));
+            check(WrapNum::neg_assign, num.neg_wrapping());
+            check(|num| num.wrapping_sub_assign(250u8), num.wrapping_sub(250u8));
+            check(|num| num.pow_assign(5), num.pow(5));
         }
     }
 
-    pub fn get_value(self) -> T {
-        self.value % self.wrap
+    #[test]
+    fn min_max_in_ring() {
+        for wrap in 1..8u8 {
+            for num in WrapNum::new(0u8, wrap) {
+                assert_ne!(num.cmp_value(num.max_in_ring()), Ordering::Greater);
+                assert_ne!(num.cmp_value(num.min_in_ring()), Ordering::Less);
+                assert_eq!(num.max_in_ring().get_wrap(), wrap);
+            }
+        }
+
+        let num = WrapNum::new(0u8, 1u8);
+        assert_eq!(num.min_in_ring(), num.max_in_ring());
     }
-}
 
-impl<T: UnsignedUnified> ToPrimitive for WrapNum<T> {
-    fn to_i64(&self) -> Option<i64> {
-        self.value.to_i64()
+    #[test]
+    fn from_u64_reduced() {
+        assert_eq!(WrapNum::from_u64_reduced(20, 6u32), WrapNum::new(2, 6));
+        assert_eq!(WrapNum::from_u64_reduced(u64::MAX, 251u8), WrapNum::new((u64::MAX % 251) as u8, 251));
     }
 
-    fn to_u64(&self) -> Option<u64> {
-        self.value.to_u64()
+    #[test]
+    fn from_i64_reduced() {
+        assert_eq!(WrapNum::from_i64_reduced(-1, 6u32), WrapNum::new(5, 6));
+        assert_eq!(WrapNum::from_i64_reduced(-13, 6u32), WrapNum::new(5, 6));
+        assert_eq!(WrapNum::from_i64_reduced(-12, 6u32), WrapNum::new(0, 6));
+        assert_eq!(WrapNum::from_i64_reduced(i64::MIN, 7u8), WrapNum::new(i64::MIN.rem_euclid(7) as u8, 7));
+        assert_eq!(WrapNum::from_i64_reduced(20, 6u16), WrapNum::new(2, 6));
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> Add<U> for WrapNum<T> {
-    type Output = Self;
+    #[test]
+    fn from_f64_reduced() {
+        assert_eq!(WrapNum::from_f64_reduced(370.5, 360u32), Some(WrapNum::new(10, 360)));
+        assert_eq!(WrapNum::from_f64_reduced(-0.5, 360u32), Some(WrapNum::new(359, 360)));
+        assert_eq!(WrapNum::from_f64_reduced(-720.0, 360u32), Some(WrapNum::new(0, 360)));
+        assert_eq!(WrapNum::from_f64_reduced(1e300, 360u32).map(|num| num.get_value() < 360), Some(true));
+        assert_eq!(WrapNum::from_f64_reduced(f64::NAN, 360u32), None);
+        assert_eq!(WrapNum::from_f64_reduced(f64::INFINITY, 360u32), None);
+        assert_eq!(WrapNum::from_f64_reduced(f64::NEG_INFINITY, 360u32), None);
+    }
 
-    fn add(self, rhs: U) -> Self::Output {
-        Self {
-            value: self.value.wrapping_add(&NumCast::from(rhs).unwrap()),
-            wrap: self.wrap
-        }
+    #[test]
+    fn from_f64_reduced_wrap_above_2_pow_53() {
+        let wrap = (1u64 << 60) + 1;
+
+        // 2^60 is exactly representable, but `wrap as f64` rounds to 2^60.
+        assert_eq!(WrapNum::from_f64_reduced((1u64 << 60) as f64, wrap), Some(WrapNum::new(1 << 60, wrap)));
+        assert_eq!(WrapNum::from_f64_reduced(-((1u64 << 60) as f64), wrap), Some(WrapNum::new(1, wrap)));
+        assert_eq!(WrapNum::from_f64_reduced(2f64.powi(61), wrap), Some(WrapNum::new(wrap - 2, wrap)));
+        assert_eq!(WrapNum::from_f64_reduced(2f64.powi(1000), 7u128), Some(WrapNum::new(2, 7)));
+        assert_eq!(WrapNum::from_f64_reduced(-0.0, wrap), Some(WrapNum::new(0, wrap)));
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> AddAssign<U> for WrapNum<T> {
-    fn add_assign(&mut self, rhs: U) {
-        self.value = self.value.wrapping_add(&NumCast::from(rhs).unwrap());
+    #[test]
+    fn ops_u128_rhs_above_type_max() {
+        let num = WrapNum::new(4u32, 7u32);
+        let big = u32::MAX as u128 * 10 + 3;
+
+        assert_eq!((num + big).get_value(), ((4 + big) % 7) as u32);
+        assert_eq!((num * big).get_value(), ((4 * big) % 7) as u32);
+        assert_eq!(num.wrapping_sub(big).get_value(), ((4 + 7 - big % 7) % 7) as u32);
+        assert_eq!((num % big).get_value(), 4);
+        assert_eq!(num.div_rem(big), (WrapNum::new(0, 7), num));
+        assert_eq!(num.wraps_in_add(u32::MAX as u128 + 3), ((u32::MAX as u128 + 7) / 7) as u32);
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> Sub<U> for WrapNum<T> {
-    type Output = Self;
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn sub_u128_rhs_above_type_max() {
+        let _ = WrapNum::new(4u32, 7u32) - (u32::MAX as u128 + 1);
+    }
 
-    fn sub(self, rhs: U) -> Self::Output {
-        Self {
-            value: self.value - NumCast::from(rhs).unwrap(),
-            wrap: self.wrap
-        }
+    #[test]
+    #[should_panic(expected = "right-hand side of type i32 is negative or not finite")]
+    fn add_negative_rhs() {
+        let _ = WrapNum::new(4u32, 7u32) + -1i32;
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> SubAssign<U> for WrapNum<T> {
-    fn sub_assign(&mut self, rhs: U) {
-        self.value = self.value - NumCast::from(rhs).unwrap();
+    #[test]
+    #[should_panic(expected = "right-hand side of type u128 doesn't fit in u8")]
+    fn gcd_rhs_too_large() {
+        WrapNum::new(0u8, 7u8).gcd(1000u128);
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> Mul<U> for WrapNum<T> {
-    type Output = Self;
+    #[test]
+    fn to_primitive_u128_above_u64() {
+        let value = u64::MAX as u128 * 5;
+        let num = WrapNum::new(value, u128::MAX);
+
+        assert_eq!(num.to_u128(), Some(value));
+        assert_eq!(num.to_i128(), Some(value as i128));
+        assert_eq!(num.to_u64(), None);
+        assert_eq!(ToPrimitive::to_u128(&&num), Some(value));
+        assert_eq!((WrapNum::new(1u128, u128::MAX) + num).get_value(), value + 1);
+    }
 
-    fn mul(self, rhs: U) -> Self::Output {
-        Self {
-            value: self.value.wrapping_mul(&NumCast::from(rhs).unwrap()),
-            wrap: self.wrap
-        }
+    #[test]
+    fn to_primitive_narrowing() {
+        let num = WrapNum::new(300u32, 1000u32);
+
+        assert_eq!(num.to_u8(), None);
+        assert_eq!(num.to_i8(), None);
+        assert_eq!(num.to_u16(), Some(300));
+        assert_eq!(num.to_usize(), Some(300));
+        assert_eq!(WrapNum::new(u32::MAX - 1, u32::MAX).to_i32(), None);
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> MulAssign<U> for WrapNum<T> {
-    fn mul_assign(&mut self, rhs: U) {
-        self.value = self.value.wrapping_mul(&NumCast::from(rhs).unwrap());
+    #[test]
+    fn div_truncates() {
+        let mut num = WrapNum::new(7u32, 10u32);
+
+        assert_eq!(num / 3u32, WrapNum::new(2, 10));
+        assert_eq!(num / u128::MAX, WrapNum::new(0, 10));
+        assert_eq!(num / 3u8 * 3u8 + num % 3u8, num);
+
+        num /= 2u8;
+        assert_eq!(num, WrapNum::new(3, 10));
+        assert_eq!(WrapNum::new(7u32, 10u32).div_mod(3), Some(WrapNum::new(9, 10)));
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> Rem<U> for WrapNum<T> {
-    type Output = Self;
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div_by_zero() {
+        let _ = WrapNum::new(8u32, 12u32) / 0u32;
+    }
 
-    fn rem(self, rhs: U) -> Self::Output {
-        Self {
-            value: self.value % NumCast::from(rhs).unwrap(),
-            wrap: self.wrap
+    // Counts events until the counter would wrap, for any checked type.
+    fn count_until_overflow<N: CheckedAdd + Copy>(mut counter: N, step: N) -> usize {
+        let mut count = 0;
+
+        while let Some(next) = counter.checked_add(&step) {
+            counter = next;
+            count += 1;
         }
+
+        count
     }
-}
 
-impl<T: UnsignedUnified, U: ToPrimitive> RemAssign<U> for WrapNum<T> {
-    fn rem_assign(&mut self, rhs: U) {
-        self.value = self.value % NumCast::from(rhs).unwrap();
+    #[test]
+    fn checked_add_detects_overflow() {
+        assert_eq!(count_until_overflow(WrapNum::new(0u8, 10u8), WrapNum::new(3u8, 10u8)), 3);
+        assert_eq!(count_until_overflow(WrapNum::new(0u8, 255u8), WrapNum::new(1u8, 255u8)), 254);
+        assert_eq!(count_until_overflow(250u8, 1u8), 5);
+        assert_eq!(WrapNum::new(u128::MAX - 1, u128::MAX).checked_add(&WrapNum::new(5, u128::MAX)), None);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn checked_sub_mul_div() {
+        let a = WrapNum::new(4u16, 10u16);
+        let b = WrapNum::new(3u16, 10u16);
+
+        assert_eq!(a.checked_sub(&b), Some(WrapNum::new(1, 10)));
+        assert_eq!(b.checked_sub(&a), None);
+        assert_eq!(CheckedMul::checked_mul(&b, &b), Some(WrapNum::new(9, 10)));
+        assert_eq!(CheckedMul::checked_mul(&a, &b), None);
+        assert_eq!(a.checked_div(&b), Some(a / b));
+        assert_eq!(a.checked_div(&b), Some(WrapNum::new(1, 10)));
+        assert_eq!(a.checked_div(&WrapNum::new(0, 10)), None);
+        assert_eq!(a.div_mod(3), Some(WrapNum::new(8, 10)));
+        assert_eq!(a.div_mod(4), None);
+    }
+
+    // a * b - c, generic over anything that wraps.
+    fn wrapping_mul_sub<N: WrappingMul + WrappingSub>(a: N, b: N, c: N) -> N {
+        a.wrapping_mul(&b).wrapping_sub(&c)
+    }
 
     #[test]
-    fn add_wrapnum_nowrap() {
-        let num1 = WrapNum::new(2u32, 6u32);
-        let num2 = WrapNum::new(2u32, 5u32);
+    fn wrapping_traits() {
+        let a = WrapNum::new(5u8, 7u8);
+        let b = WrapNum::new(4u8, 7u8);
+
+        assert_eq!(wrapping_mul_sub(a, b, WrapNum::new(6, 7)), WrapNum::new(0, 7));
+        assert_eq!(wrapping_mul_sub(a, a, b), WrapNum::new(0, 7));
+        assert_eq!(wrapping_mul_sub(20u8, 20u8, 200u8), 200);
+        assert_eq!(WrappingAdd::wrapping_add(&a, &b), WrapNum::new(2, 7));
+        assert_eq!(WrappingSub::wrapping_sub(&b, &a), WrapNum::new(6, 7));
+    }
 
-        let num3 = num1 + num2;
+    #[test]
+    fn try_add_mul() {
+        let num = WrapNum::new(5u8, 7u8);
+
+        assert_eq!(num.try_add(4u64), Ok(WrapNum::new(2, 7)));
+        assert_eq!(num.try_mul(u128::MAX), Ok(num * u128::MAX));
+        assert_eq!(num.try_add(-1i32), Err(WrapError(())));
+        assert_eq!(num.try_mul(f64::NAN), Err(WrapError(())));
+        assert_eq!(num.try_add(-1.5f32).unwrap_err().to_string(), "right-hand side is negative or not finite");
+    }
+
+    // Gauge that fills by `amount * factor`, then drains by `amount`.
+    fn fill_then_drain<N: SaturatingAdd + SaturatingSub + SaturatingMul>(level: N, amount: N, factor: N) -> (N, N) {
+        let full = level.saturating_add(&amount.saturating_mul(&factor));
 
-        assert_eq!(num3.get_value(), 4);
-        assert_eq!(num3.wrap, 6);
+        (full.saturating_sub(&amount), full)
     }
 
     #[test]
-    fn add_wrapnum_wrap() {
-        let num1 = WrapNum::new(3u32, 6u32);
-        let num2 = WrapNum::new(4u32, 5u32);
+    fn saturating_traits() {
+        let level = WrapNum::new(3u8, 10u8);
 
-        let num3 = num1 + num2;
+        let (drained, full) = fill_then_drain(level, WrapNum::new(2, 10), WrapNum::new(2, 10));
+        assert_eq!((drained, full), (WrapNum::new(5, 10), WrapNum::new(7, 10)));
 
-        assert_eq!(num3.get_value(), 1);
-        assert_eq!(num3.wrap, 6);
+        let (drained, full) = fill_then_drain(level, WrapNum::new(4, 10), WrapNum::new(3, 10));
+        assert_eq!((drained, full), (WrapNum::new(5, 10), WrapNum::new(9, 10)));
+
+        let (drained, full) = fill_then_drain(WrapNum::new(1u8, 10u8), WrapNum::new(3, 10), WrapNum::new(0, 10));
+        assert_eq!((drained, full), (WrapNum::new(0, 10), WrapNum::new(1, 10)));
+
+        assert_eq!(fill_then_drain(200u8, 100u8, 2u8), (155, 255));
     }
 
     #[test]
-    fn add_u32_wrap() {
-        let num1 = WrapNum::new(3u32, 6u32);
-        let num2 = 7u32;
+    fn saturating_inherent() {
+        let num = WrapNum::new(u128::MAX - 2, u128::MAX);
 
-        let num3 = num1 + num2;
+        assert_eq!(num.saturating_add(u128::MAX).get_value(), u128::MAX - 1);
+        assert_eq!(num.saturating_mul(2u8).get_value(), u128::MAX - 1);
+        assert_eq!(WrapNum::new(3u8, 10u8).saturating_sub(300u16), WrapNum::new(0, 10));
+    }
 
-        assert_eq!(num3.get_value(), 4);
-        assert_eq!(num3.wrap, 6);
+    #[test]
+    fn euclid_matches_primitive() {
+        for a in 0..20u8 {
+            for b in 1..30u8 {
+                let (num, rhs) = (WrapNum::new(a, 20), WrapNum::new(b, 30));
+
+                assert_eq!(Euclid::div_euclid(&num, &rhs), WrapNum::new(a.div_euclid(b), 20));
+                assert_eq!(Euclid::rem_euclid(&num, &rhs), WrapNum::new(a.rem_euclid(b), 20));
+            }
+        }
     }
 
     #[test]
-    fn sub_u32() {
-        let num1 = WrapNum::new(5u32, 6u32);
-        let num2 = 2u32;
+    fn checked_euclid_zero() {
+        let num = WrapNum::new(7u32, 10u32);
 
-        let num3 = num1 - num2;
+        assert_eq!(num.checked_div_euclid(&WrapNum::new(0, 5)), None);
+        assert_eq!(num.checked_rem_euclid(&WrapNum::new(0, 5)), None);
+        assert_eq!(num.checked_div_euclid(&WrapNum::new(2, 5)), Some(WrapNum::new(3, 10)));
+    }
 
-        assert_eq!(num3.get_value(), 3);
-        assert_eq!(num3.wrap, 6);
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div_euclid_zero() {
+        Euclid::div_euclid(&WrapNum::new(7u32, 10u32), &WrapNum::new(0, 10));
     }
 
     #[test]
-    #[should_panic]
-    fn sub_u32_overflow() {
-        let num1 = WrapNum::new(5u32, 7u32);
-        let num2 = 6u32;
+    fn cmp_primitive() {
+        let num = WrapNum::new(5u32, 10u32);
+
+        assert!(num < 6u32);
+        assert!(num > 4u8);
+        assert!(num == 5u64);
+        assert!(num <= 5usize && num >= 5i16);
+        assert!(num != 15u32);
+        assert!(num < 10u32 && num < u128::MAX);
+        assert!(num > -1i32 && num > i128::MIN);
+        assert!(WrapNum::new(9u8, 10u8) < 200u8);
+    }
 
-        let _ = num1 - num2;
+    #[test]
+    fn cmp_primitive_reflected() {
+        let num = WrapNum::new(5u32, 10u32);
+
+        assert!(5u32 == num && 5u8 == num);
+        assert!(6u64 > num && 4i32 < num);
+        assert!(15u32 != num && 15u32 > num);
+        assert!(-1i64 < num);
+        assert_eq!(3u16.partial_cmp(&num), Some(Ordering::Less));
+    }
+
+    fn cube<N: Pow<u32, Output = N>>(base: N) -> N {
+        base.pow(3u32)
     }
 
     #[test]
-    fn add_assign_wrapnum_wrap() {
-        let mut num1 = WrapNum::new(3u32, 6u32);
-        let num2 = WrapNum::new(4u32, 5u32);
+    fn pow_trait() {
+        assert_eq!(cube(WrapNum::new(4u8, 10u8)), WrapNum::new(4, 10));
+        assert_eq!(cube(4u8), 64);
+        assert_eq!(Pow::pow(WrapNum::new(3u32, 7u32), WrapNum::new(6u32, 100u32)), WrapNum::new(1, 7));
+        assert_eq!(Pow::pow(WrapNum::new(3u32, 7u32), 0u32), WrapNum::new(1, 7));
+        assert_eq!(Pow::pow(WrapNum::new(0u32, 1u32), 0u64), WrapNum::new(0, 1));
+    }
 
-        num1 += num2;
+    #[test]
+    fn pow_trait_against_big_uint() {
+        let mut rng = crate::test_rng::TestRng::new(0xbeef);
 
-        assert_eq!(num1.get_value(), 1);
-        assert_eq!(num1.wrap, 6);
+        for _ in 0..200 {
+            let wrap = u64::MAX - rng.below(1 << 20);
+            let (value, exp) = (rng.below(wrap), rng.next_u64());
+
+            let expected = num::BigUint::from(value).modpow(&num::BigUint::from(exp), &num::BigUint::from(wrap));
+
+            assert_eq!(num::BigUint::from(Pow::pow(WrapNum::new(value, wrap), exp).get_value()), expected);
+        }
     }
 
     #[test]
-    fn mul_wrapnum_wrap() {
-        let num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u32, 5u32);
+    fn round_to_multiple() {
+        assert_eq!(WrapNum::new(7u8, 20u8).round_to_multiple(5), WrapNum::new(5, 20));
+        assert_eq!(WrapNum::new(8u8, 20u8).round_to_multiple(5), WrapNum::new(10, 20));
+        assert_eq!(WrapNum::new(5u8, 20u8).round_to_multiple(10), WrapNum::new(10, 20));
+        assert_eq!(WrapNum::new(19u8, 20u8).round_to_multiple(5), WrapNum::new(0, 20));
+        assert_eq!(WrapNum::new(21u8, 22u8).round_to_multiple(5), WrapNum::new(20, 22));
+        assert_eq!(WrapNum::new(254u8, 255u8).round_to_multiple(128), WrapNum::new(1, 255));
+    }
 
-        let num3 = num1 * num2;
+    #[test]
+    fn round_to_multiple_near_u128_max() {
+        let num = WrapNum::new(u128::MAX - 1, u128::MAX);
+        let k = (1 << 127) + 5;
+
+        // Rounds up to 2k = 2^128 + 10, which is 11 modulo 2^128 - 1.
+        assert_eq!(num.round_to_multiple(k).get_value(), 11);
+        assert_eq!(num.round_to_multiple(u128::MAX - 2).get_value(), u128::MAX - 2);
+        assert_eq!(WrapNum::new(1u128, u128::MAX).round_to_multiple(k).get_value(), 0);
+    }
 
-        assert_eq!(num3.get_value(), 4);
-        assert_eq!(num3.wrap, 6);
+    #[test]
+    #[should_panic(expected = "attempt to round to a multiple of zero")]
+    fn round_to_multiple_of_zero() {
+        WrapNum::new(7u8, 20u8).round_to_multiple(0);
     }
 
     #[test]
-    fn mul_assign_wrapnum_wrap() {
-        let mut num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u32, 5u32);
+    fn lerp() {
+        let (a, b) = (WrapNum::new(1u32, 12u32), WrapNum::new(5u32, 12u32));
 
-        num1 *= num2;
+        assert_eq!(a.lerp(b, 0.5), WrapNum::new(3, 12));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(b.lerp(a, 0.25), WrapNum::new(4, 12));
+    }
 
-        assert_eq!(num1.get_value(), 4);
-        assert_eq!(num1.wrap, 6);
+    #[test]
+    fn lerp_across_seam() {
+        let (a, b) = (WrapNum::new(10u32, 12u32), WrapNum::new(2u32, 12u32));
+
+        assert_eq!(a.lerp(b, 0.5), WrapNum::new(0, 12));
+        assert_eq!(b.lerp(a, 0.5), WrapNum::new(0, 12));
+        assert_eq!(b.lerp(a, 1.0), a);
+        assert_eq!(WrapNum::new(0u32, 12u32).lerp(WrapNum::new(6, 12), 0.5), WrapNum::new(3, 12));
+        assert_eq!(WrapNum::new(6u32, 12u32).lerp(WrapNum::new(0, 12), 0.5), WrapNum::new(9, 12));
     }
 
     #[test]
-    fn ops_different_types() {
-        let mut num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u16, 5u16);
+    #[should_panic(expected = "t must be in [0, 1]")]
+    fn lerp_t_out_of_range() {
+        WrapNum::new(0u32, 12u32).lerp(WrapNum::new(6, 12), 1.5);
+    }
 
-        num1 *= num2;
+    // Solves the 2x2 system [a b; c d] x = [e f] by elimination, for any
+    // field-like type.
+    fn solve_2x2<N>(a: N, b: N, c: N, d: N, e: N, f: N) -> (N, N)
+    where
+        N: Copy + Inv<Output = N> + std::ops::Mul<N, Output = N> + WrappingSub,
+    {
+        let factor = c * a.inv();
+        let d = d.wrapping_sub(&(factor * b));
+        let f = f.wrapping_sub(&(factor * e));
+        let y = f * d.inv();
+
+        (e.wrapping_sub(&(b * y)) * a.inv(), y)
+    }
 
-        assert_eq!(num1.get_value(), 4);
-        assert_eq!(num1.wrap, 6);
+    #[test]
+    fn inv_gaussian_elimination() {
+        let n = |v| WrapNum::new(v, 13u32);
+
+        // 2x + 3y = 1, 5x + 7y = 4 (mod 13).
+        let (x, y) = solve_2x2(n(2), n(3), n(5), n(7), n(1), n(4));
+
+        assert_eq!(n(2) * x + n(3) * y, n(1));
+        assert_eq!(n(5) * x + n(7) * y, n(4));
+        assert_eq!(n(5).inv(), n(8));
     }
 
     #[test]
-    fn rem_u32() {
-        let num1 = WrapNum::new(5u32, 6u32);
-        let num2 = 2u32;
+    #[should_panic(expected = "value has no inverse modulo the wrap")]
+    fn inv_non_unit() {
+        let _ = WrapNum::new(6u32, 9u32).inv();
+    }
 
-        let num3 = num1 % num2;
+    #[test]
+    fn mul_add_matches_separate_ops() {
+        let mut rng = crate::test_rng::TestRng::new(0xadd);
 
-        assert_eq!(num3.get_value(), 1);
-        assert_eq!(num3.wrap, 6);
+        for _ in 0..500 {
+            let wrap = u128::MAX - rng.next_u64() as u128;
+            let num = WrapNum::new(rng.next_u128() % wrap, wrap);
+            let (a, b) = (WrapNum::new(rng.next_u128() % wrap, wrap), rng.next_u128());
+
+            assert_eq!(num.mul_add(a, b), num * a + b);
+
+            let wrap = u64::MAX - rng.below(1 << 16);
+            let num = WrapNum::new(rng.below(wrap), wrap);
+            let (a, b) = (rng.next_u64(), WrapNum::new(rng.below(wrap), wrap));
+
+            let mut fused = num;
+            fused.mul_add_assign(a, b);
+            assert_eq!(fused, num * a + b);
+        }
     }
 
     #[test]
-    fn rem_assign_u32() {
-        let mut num1 = WrapNum::new(9u32, 10u32);
-        let num2 = 5u32;
+    fn not_mirrors() {
+        assert_eq!(!WrapNum::new(0u8, 6u8), WrapNum::new(5, 6));
+        assert_eq!(!WrapNum::new(5u8, 6u8), WrapNum::new(0, 6));
+        assert_eq!(!WrapNum::new(2u8, 5u8), WrapNum::new(2, 5));
+        assert_eq!(!WrapNum::new(3u8, 8u8), WrapNum::new(!3u8 & 7, 8));
+        assert_eq!(!WrapNum::new(0u8, 1u8), WrapNum::new(0, 1));
+    }
 
-        num1 %= num2;
+    #[test]
+    fn ord_sorts_values_then_wraps() {
+        let mut rng = crate::test_rng::TestRng::new(0x5047);
+        let mut nums: Vec<_> = (0..10u32).map(|v| WrapNum::new(v, 10)).collect();
+        nums.extend([WrapNum::new(3, 4), WrapNum::new(3, 20)]);
 
-        assert_eq!(num1.get_value(), 4);
-        assert_eq!(num1.wrap, 10);
+        for i in (1..nums.len()).rev() {
+            nums.swap(i, rng.below(i as u64 + 1) as usize);
+        }
+        nums.sort();
+
+        let values: Vec<_> = nums.iter().map(|num| (num.get_value(), num.get_wrap())).collect();
+        assert_eq!(values[..6], [(0, 10), (1, 10), (2, 10), (3, 4), (3, 10), (3, 20)]);
+        assert!(nums.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let (nine, zero) = (WrapNum::new(9u8, 10u8), WrapNum::new(0u8, 10u8));
+        assert!(nine > zero && nine.serial_lt(&zero));
     }
 
     #[test]
-    fn hash_eq() {
-        use std::hash::{Hasher, DefaultHasher};
+    fn ord_btree_map_keys() {
+        let mut map = std::collections::BTreeMap::new();
 
-        fn calculate_hash<T: Hash>(t: &T) -> u64 {
-            let mut s = DefaultHasher::new();
-            t.hash(&mut s);
-            s.finish()
+        map.insert(WrapNum::new(5u16, 6u16), "five");
+        map.insert(WrapNum::new(4u16, 6u16) + 7u16, "eleven");
+        map.insert(WrapNum::new(1u16, 6u16), "one");
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), ["one", "eleven"]);
+    }
+
+    #[test]
+    fn ord_consistent_with_eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |num: &WrapNum<u32>| {
+            let mut hasher = DefaultHasher::new();
+            num.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let pairs = [
+            (WrapNum::new(3, 7), WrapNum::new(5, 7) + 5u32),
+            (WrapNum::new(3, 7), WrapNum::new(3, 8)),
+            (WrapNum::new(2, 7), WrapNum::new(3, 7)),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(a.cmp(&b) == Ordering::Equal, a == b);
+            if a == b {
+                assert_eq!(hash(&a), hash(&b));
+            }
         }
+    }
 
-        let num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u32, 6u32);
+    #[test]
+    fn add_nested_two_digit_odometer() {
+        let (mut ones, mut tens) = (WrapNum::new(7u32, 10u32), WrapNum::new(9u32, 10u32));
 
-        assert_eq!(calculate_hash(&num1), calculate_hash(&num2));
+        let (next, cycles) = ones.add_nested(5, 10);
+        (ones, tens) = (next, tens + cycles);
+        assert_eq!((tens.get_value(), ones.get_value()), (0, 2));
+
+        let (next, cycles) = ones.add_nested(123, 10);
+        (ones, tens) = (next, tens + cycles);
+        assert_eq!((tens.get_value(), ones.get_value()), (2, 5));
+
+        assert_eq!(ones.add_nested(4, 10).1, WrapNum::new(0, 10));
     }
 
     #[test]
-    fn hash_ne() {
-        use std::hash::{Hasher, DefaultHasher};
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn add_nested_zero_outer_wrap() {
+        let _ = WrapNum::new(7u32, 10u32).add_nested(5, 0);
+    }
 
-        fn calculate_hash<T: Hash>(t: &T) -> u64 {
-            let mut s = DefaultHasher::new();
-            t.hash(&mut s);
-            s.finish()
+    #[test]
+    fn from_fraction() {
+        assert_eq!(WrapNum::from_fraction(1u32, 4, 360), WrapNum::new(90, 360));
+        assert_eq!(WrapNum::from_fraction(3u32, 4, 360), WrapNum::new(270, 360));
+        assert_eq!(WrapNum::from_fraction(1u32, 3, 360), WrapNum::new(120, 360));
+        assert_eq!(WrapNum::from_fraction(1u32, 3, 100), WrapNum::new(33, 100));
+        assert_eq!(WrapNum::from_fraction(2u32, 3, 100), WrapNum::new(66, 100));
+        assert_eq!(WrapNum::from_fraction(5u32, 4, 360), WrapNum::new(90, 360));
+        assert_eq!(WrapNum::from_fraction(4u32, 4, 360), WrapNum::new(0, 360));
+    }
+
+    #[test]
+    fn from_fraction_near_u128_max() {
+        let max = u128::MAX;
+
+        assert_eq!(WrapNum::from_fraction(max - 1, max, max).get_value(), max - 1);
+        assert_eq!(WrapNum::from_fraction(1, 2, max).get_value(), max / 2);
+        assert_eq!(WrapNum::from_fraction(max / 3, max, max - 4).get_value(), (max - 4) / 3);
+    }
+
+    #[test]
+    fn from_fraction_matches_u128() {
+        let mut rng = crate::test_rng::TestRng::new(0xf4ac);
+
+        for _ in 0..500 {
+            let (wrap, denom) = (rng.next_u64() | 1, rng.next_u64() | 1);
+            let numer = rng.below(denom);
+
+            let expected = numer as u128 * wrap as u128 / denom as u128;
+
+            assert_eq!(WrapNum::from_fraction(numer, denom, wrap).get_value() as u128, expected);
         }
+    }
 
-        let num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u32, 5u32);
+    #[test]
+    #[should_panic(expected = "denominator must be nonzero")]
+    fn from_fraction_zero_denominator() {
+        let _ = WrapNum::from_fraction(1u32, 0, 360);
+    }
 
-        assert_ne!(calculate_hash(&num1), calculate_hash(&num2));
+    #[test]
+    fn cmp_circular_half_way() {
+        let even = |v| WrapNum::new(v, 10u8);
+        let odd = |v| WrapNum::new(v, 9u8);
+
+        assert_eq!(even(2).cmp_circular(&even(7)), None);
+        assert_eq!(even(2).cmp_circular(&even(6)), Some(Ordering::Less));
+        assert_eq!(even(2).cmp_circular(&even(8)), Some(Ordering::Greater));
+        assert_eq!(odd(2).cmp_circular(&odd(6)), Some(Ordering::Less));
+        assert_eq!(odd(2).cmp_circular(&odd(7)), Some(Ordering::Greater));
+        assert_eq!(odd(2).cmp_circular(&odd(2)), Some(Ordering::Equal));
     }
 
     #[test]
-    fn eq() {
-        let num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u32, 6u32);
+    fn cmp_circular_across_seam() {
+        let frame = |v| WrapNum::new(v, 256u16);
 
-        assert_eq!(num1 == num2, true);
+        assert_eq!(frame(250).cmp_circular(&frame(3)), Some(Ordering::Less));
+        assert_eq!(frame(3).cmp_circular(&frame(250)), Some(Ordering::Greater));
+        assert_eq!(frame(0).cmp_circular(&frame(128)), None);
     }
 
     #[test]
-    fn ne() {
-        let num1 = WrapNum::new(4u32, 6u32);
-        let num2 = WrapNum::new(4u32, 5u32);
+    #[should_panic(expected = "wrap values differ")]
+    fn cmp_circular_different_wraps() {
+        WrapNum::new(1u8, 10u8).cmp_circular(&WrapNum::new(1u8, 12u8));
+    }
+
+    #[test]
+    fn checked_mul_inherent() {
+        let num = WrapNum::new(6u8, 20u8);
+
+        assert_eq!(num.checked_mul(3u8), Some(WrapNum::new(18, 20)));
+        assert_eq!(num.checked_mul(4u8), None);
+        assert_eq!(num.checked_mul(0u8), Some(WrapNum::new(0, 20)));
+        assert_eq!(num.checked_mul(u128::MAX), None);
+        assert_eq!(WrapNum::new(0u8, 20u8).checked_mul(u128::MAX), Some(WrapNum::new(0, 20)));
+    }
+
+    #[test]
+    fn new_reduced() {
+        assert_eq!(WrapNum::new_reduced(17u8, 6u8), WrapNum::new(5, 6));
+        assert_eq!(WrapNum::new_reduced(5u8, 6u8), WrapNum::new(5, 6));
+        assert_eq!(WrapNum::new_reduced(u64::MAX, 10u64), WrapNum::new(5, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn new_reduced_zero_wrap() {
+        let _ = WrapNum::new_reduced(3u8, 0u8);
+    }
+
+    fn total<V: AsRef<u64>>(items: &[V]) -> u64 {
+        items.iter().map(|item| *item.as_ref()).sum()
+    }
+
+    #[test]
+    fn as_ref_value() {
+        let nums = [WrapNum::new(3u64, 10u64) + 9u64, WrapNum::new(7u64, 8u64)];
+
+        assert_eq!(total(&nums), 9);
+        assert_eq!(nums[0].as_value(), &2);
+        assert_eq!(nums[1].as_value_ref(), nums[1].as_ref());
+    }
+
+    #[test]
+    fn is_ahead_of_across_seam() {
+        let seq = |v| WrapNum::new(v, 65536u32);
+
+        assert!(seq(2).is_ahead_of(seq(65530), 32768));
+        assert!(seq(2) < seq(65530));
+        assert!(!seq(65530).is_ahead_of(seq(2), 32768));
+        assert!(seq(32768).is_ahead_of(seq(0), 32768));
+        assert!(!seq(32769).is_ahead_of(seq(0), 32768));
+        assert!(!seq(7).is_ahead_of(seq(7), 32768));
+    }
+
+    #[test]
+    fn is_ahead_of_window() {
+        let num = WrapNum::new(1u8, 10u8);
+
+        assert!(num.is_ahead_of(WrapNum::new(8, 10), 3));
+        assert!(!num.is_ahead_of(WrapNum::new(8, 10), 2));
+        assert!(num.is_ahead_of(WrapNum::new(2, 10), 200));
+        assert!(!num.is_ahead_of(WrapNum::new(0, 10), 0));
+    }
+
+    #[test]
+    fn as_usize_indexing() {
+        let buf = ['a', 'b', 'c', 'd', 'e'];
+        let mut pos = WrapNum::new(3u8, 5u8);
+
+        assert_eq!(buf[pos.as_usize()], 'd');
+        pos += 4u8;
+        assert_eq!(buf[pos.as_usize()], 'c');
+        assert_eq!(pos.as_u64(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "value must fit in u64")]
+    fn as_u64_too_large() {
+        WrapNum::new(u128::MAX - 1, u128::MAX).as_u64();
+    }
+
+    #[test]
+    fn bytes_round_trip_u16() {
+        let num = WrapNum::new(0x1234u16, 0xabcdu16);
+
+        assert_eq!(num.to_le_bytes(), ([0x34, 0x12], [0xcd, 0xab]));
+        assert_eq!(num.to_be_bytes(), ([0x12, 0x34], [0xab, 0xcd]));
+
+        let (value, wrap) = num.to_le_bytes();
+        assert_eq!(WrapNum::from_le_bytes(&value, &wrap), Some(num));
+        let (value, wrap) = num.to_be_bytes();
+        assert_eq!(WrapNum::from_be_bytes(&value, &wrap), Some(num));
+        let (value, wrap) = num.to_ne_bytes();
+        assert_eq!(WrapNum::from_ne_bytes(&value, &wrap), Some(num));
+    }
+
+    #[test]
+    fn bytes_round_trip_u32() {
+        let num = WrapNum::new(359u32, 360u32) + 5u32;
 
-        assert_eq!(num1 == num2, false);
+        let (value, wrap) = num.to_be_bytes();
+
+        assert_eq!((value, wrap), ([0, 0, 0, 4], [0, 0, 1, 104]));
+        assert_eq!(WrapNum::<u32>::from_be_bytes(&value, &wrap), Some(num));
+    }
+
+    #[test]
+    fn from_bytes_validates() {
+        assert_eq!(WrapNum::<u32>::from_le_bytes(&[5, 0, 0, 0], &[5, 0, 0, 0]), None);
+        assert_eq!(WrapNum::<u32>::from_le_bytes(&[0, 0, 0, 0], &[0, 0, 0, 0]), None);
+        assert_eq!(WrapNum::<u16>::from_be_bytes(&[0, 4], &[0, 5]), Some(WrapNum::new(4, 5)));
     }
 }
@@ -0,0 +1,187 @@
+use std::fmt;
+use std::error::Error;
+use crate::wrap_num::{WrapNum, UnsignedUnified};
+
+// Residues sharing a single wrap, stored as plain values.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct WrapVec<T: UnsignedUnified> {
+    values: Vec<T>,
+    wrap: T,
+}
+
+// Error returned when combining two WrapVecs that don't line up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapVecError {
+    WrapMismatch,
+    LengthMismatch,
+}
+
+impl fmt::Display for WrapVecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WrapVecError::WrapMismatch => f.write_str("wrap values differ"),
+            WrapVecError::LengthMismatch => f.write_str("lengths differ"),
+        }
+    }
+}
+
+impl Error for WrapVecError {}
+
+impl<T: UnsignedUnified> WrapVec<T> {
+    pub fn new(wrap: T) -> WrapVec<T> {
+        WrapVec::from_raw_parts(Vec::new(), wrap)
+    }
+
+    // Every value is reduced modulo `wrap`.
+    pub fn from_raw_parts(mut values: Vec<T>, wrap: T) -> WrapVec<T> {
+        assert!(wrap != T::zero(), "wrap must be nonzero");
+
+        for value in values.iter_mut() {
+            *value = *value % wrap;
+        }
+
+        WrapVec {
+            values,
+            wrap,
+        }
+    }
+
+    pub fn into_raw_parts(self) -> (Vec<T>, T) {
+        (self.values, self.wrap)
+    }
+
+    pub fn get_wrap(&self) -> T {
+        self.wrap
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Reduces `value` modulo the wrap before storing it.
+    pub fn push(&mut self, value: T) {
+        self.values.push(value % self.wrap);
+    }
+
+    pub fn get(&self, index: usize) -> Option<WrapNum<T>> {
+        self.values.get(index).map(|&value| WrapNum::new(value, self.wrap))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = WrapNum<T>> + '_ {
+        self.values.iter().map(|&value| WrapNum::new(value, self.wrap))
+    }
+
+    pub fn add_scalar(&mut self, k: T) {
+        let wrap = self.wrap;
+
+        for value in self.values.iter_mut() {
+            *value = (WrapNum::new(*value, wrap) + k).get_value();
+        }
+    }
+
+    pub fn add_elementwise(&mut self, other: &WrapVec<T>) -> Result<(), WrapVecError> {
+        if self.wrap != other.wrap {
+            return Err(WrapVecError::WrapMismatch);
+        }
+        if self.len() != other.len() {
+            return Err(WrapVecError::LengthMismatch);
+        }
+
+        let wrap = self.wrap;
+        for (value, &rhs) in self.values.iter_mut().zip(other.values.iter()) {
+            *value = (WrapNum::new(*value, wrap) + rhs).get_value();
+        }
+
+        Ok(())
+    }
+
+    pub fn sum(&self) -> WrapNum<T> {
+        self.iter().fold(WrapNum::new(T::zero(), self.wrap), |sum, num| sum + num)
+    }
+
+    // The empty product is 1, which is 0 when wrap == 1.
+    pub fn product(&self) -> WrapNum<T> {
+        let one = WrapNum::new(T::zero(), self.wrap) + T::one();
+
+        self.iter().fold(one, |product, num| product * num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw_parts_reduces() {
+        let vec = WrapVec::from_raw_parts(vec![1u32, 7, 12], 6);
+
+        assert_eq!(vec.into_raw_parts(), (vec![1, 1, 0], 6));
+    }
+
+    #[test]
+    #[should_panic(expected = "wrap must be nonzero")]
+    fn from_raw_parts_zero_wrap() {
+        let _ = WrapVec::from_raw_parts(vec![1u32], 0);
+    }
+
+    #[test]
+    fn push_and_get() {
+        let mut vec = WrapVec::new(6u8);
+
+        vec.push(3);
+        vec.push(200);
+
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec.get(1), Some(WrapNum::new(2, 6)));
+        assert_eq!(vec.get(2), None);
+        assert_eq!(vec.iter().collect::<Vec<_>>(), vec![WrapNum::new(3, 6), WrapNum::new(2, 6)]);
+    }
+
+    #[test]
+    fn add_scalar_against_naive() {
+        let raw = vec![0u32, 5, 9, 10, 3];
+        let mut vec = WrapVec::from_raw_parts(raw.clone(), 11);
+
+        vec.add_scalar(7);
+
+        let naive: Vec<_> = raw.iter().map(|&value| (WrapNum::new(value, 11) + 7u32).get_value()).collect();
+        assert_eq!(vec.into_raw_parts().0, naive);
+    }
+
+    #[test]
+    fn add_elementwise_against_naive() {
+        let lhs = vec![0u16, 5, 9, 10, 3];
+        let rhs = vec![10u16, 6, 2, 10, 0];
+        let mut vec = WrapVec::from_raw_parts(lhs.clone(), 11);
+
+        vec.add_elementwise(&WrapVec::from_raw_parts(rhs.clone(), 11)).unwrap();
+
+        let naive: Vec<_> = lhs.iter().zip(rhs.iter())
+            .map(|(&a, &b)| (WrapNum::new(a, 11) + b).get_value())
+            .collect();
+        assert_eq!(vec.into_raw_parts().0, naive);
+    }
+
+    #[test]
+    fn add_elementwise_mismatch() {
+        let mut vec = WrapVec::from_raw_parts(vec![1u32, 2], 6);
+
+        assert_eq!(vec.add_elementwise(&WrapVec::from_raw_parts(vec![1, 2], 7)), Err(WrapVecError::WrapMismatch));
+        assert_eq!(vec.add_elementwise(&WrapVec::from_raw_parts(vec![1], 6)), Err(WrapVecError::LengthMismatch));
+        assert_eq!(vec.into_raw_parts().0, vec![1, 2]);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let vec = WrapVec::from_raw_parts(vec![4u32, 5, 6], 7);
+
+        assert_eq!(vec.sum(), WrapNum::new(1, 7));
+        assert_eq!(vec.product(), WrapNum::new(1, 7));
+        assert_eq!(WrapVec::new(7u32).product(), WrapNum::new(1, 7));
+        assert_eq!(WrapVec::new(1u32).product(), WrapNum::new(0, 1));
+    }
+}